@@ -1,5 +1,5 @@
-use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
@@ -7,37 +7,46 @@ pub enum Zone {
     Local,
     Utc,
     Fixed(i32),
+    /// IANA time zone name like `Asia/Tehran`, the date is the local day in that zone
+    Iana(String),
 }
 
 use praytimes::{
-    methods,
+    methods::MethodRegistry,
     types::{
-        AsrFactor, CalculationUnit, Degrees, HighLatsMethod, MidnightMethod, Minutes, Parameters,
+        AsrFactor, CalculationUnit, HighLatsMethod, HijriRule, Ihtiyat, MidnightMethod, Minutes,
+        Parameters, RoundingPolicy,
     },
 };
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ValueEnum)]
-pub enum SelectedMethod {
-    MWL,
-    ISNA,
-    Egypt,
-    Makkah,
-    Karachi,
-    Tehran,
-    Jafari,
+/// id or alias of a method of the [`MethodRegistry`], checked when it's deserialized
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SelectedMethod(String);
+
+impl TryFrom<String> for SelectedMethod {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        match MethodRegistry::default().get(&name) {
+            Some(_) => Ok(Self(name)),
+            None => Err(format!("unknown method {name}")),
+        }
+    }
+}
+
+impl From<SelectedMethod> for String {
+    fn from(method: SelectedMethod) -> Self {
+        method.0
+    }
 }
 
 impl SelectedMethod {
     pub(crate) fn get_method(&self) -> Parameters {
-        match self {
-            SelectedMethod::MWL => methods::MUSLIM_WORLD_LEAGUE,
-            SelectedMethod::ISNA => methods::ISLAMIC_SOCIETY_OF_NORTH_AMERICA,
-            SelectedMethod::Egypt => methods::EGYPTIAN_GENERAL_AUTHORITY_OF_SURVEY,
-            SelectedMethod::Makkah => methods::UMM_AL_QURA_UNIVERSITY_MAKKAH,
-            SelectedMethod::Karachi => methods::UNIVERSITY_OF_ISLAMIC_SCIENCES_KARACHI,
-            SelectedMethod::Tehran => methods::INSTITUTE_OF_GEOPHYSICS_UNIVERSITY_OF_TEHRAN,
-            SelectedMethod::Jafari => methods::SHIA_ITHNA_ASHARI_LEVA_INSTITUTE_QUM,
-        }
+        MethodRegistry::default()
+            .get(&self.0)
+            .map(|method| method.parameters.clone())
+            .expect("the method is checked on deserialization")
     }
 }
 
@@ -69,6 +78,12 @@ impl CustomizableParams {
                     isha: partial.isha.unwrap_or(method.isha),
                     midnight: partial.midnight.unwrap_or(method.midnight),
                     high_latitudes: partial.high_latitudes.unwrap_or(method.high_latitudes),
+                    hijri_rules: partial
+                        .hijri_rules
+                        .map(Cow::Owned)
+                        .unwrap_or(method.hijri_rules),
+                    ihtiyat: partial.ihtiyat.unwrap_or(method.ihtiyat),
+                    rounding: partial.rounding.unwrap_or(method.rounding),
                 }
             }
             CustomizableParams::MethodAndExtra {
@@ -82,7 +97,7 @@ impl CustomizableParams {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PartialParameters {
     pub imsak: Option<CalculationUnit>,
-    pub fajr: Option<Degrees>,
+    pub fajr: Option<CalculationUnit>,
     pub dhuhr: Option<Minutes>,
     pub asr: Option<AsrFactor>,
     pub maghrib: Option<CalculationUnit>,
@@ -90,4 +105,8 @@ pub struct PartialParameters {
     pub midnight: Option<MidnightMethod>,
     #[serde(rename = "highLats")]
     pub high_latitudes: Option<HighLatsMethod>,
+    #[serde(rename = "hijriRules")]
+    pub hijri_rules: Option<Vec<HijriRule>>,
+    pub ihtiyat: Option<Ihtiyat>,
+    pub rounding: Option<RoundingPolicy>,
 }
@@ -0,0 +1,86 @@
+use std::path::PathBuf;
+
+use chrono::{Datelike, Local, NaiveDate};
+use clap::Parser;
+use praytimes::{
+    lunar::{self, CrescentVisibility},
+    solar::Almanac,
+    types::{format_time, Location},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Parser, Debug, Clone)]
+#[command(author, version, about, long_about = None)]
+pub struct Args {
+    /// configuration file
+    #[arg(short, long)]
+    config: PathBuf,
+
+    /// date of the evening ( default is today )
+    #[arg(short, long,default_value_t = get_today())]
+    pub date: NaiveDate,
+
+    /// strftime compatible format ( overwrites the config file's format field )
+    #[arg(short, long)]
+    pub format: Option<String>,
+
+    /// whether to output as json format or not
+    #[arg(short, long, default_value_t = false)]
+    pub json: bool,
+}
+
+fn get_today() -> NaiveDate {
+    let date_time = Local::now();
+    NaiveDate::from_ymd_opt(date_time.year(), date_time.month(), date_time.day()).unwrap()
+}
+
+fn default_format() -> String {
+    "%F %T".into()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Config {
+    location: Location,
+    #[serde(default = "default_format")]
+    format: String,
+}
+
+pub fn run(args: Args) {
+    let conf = std::fs::read(args.config).expect("failed to open file");
+    let conf: Config = serde_json::from_slice(&conf).expect("failed to read configuration");
+
+    let Some(report) = lunar::crescent_visibility(&conf.location, &args.date, &Almanac) else {
+        eprintln!("the sun or the moon doesn't set on {}", args.date);
+        std::process::exit(1);
+    };
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&report).unwrap();
+        println!("{json}");
+    } else {
+        print_report(&report, &args.format.unwrap_or(conf.format));
+    }
+}
+
+fn print_report(report: &CrescentVisibility, format: &str) {
+    let time = |t| format_time(t, format, &Local);
+
+    println!("conjunction\t{}", time(report.conjunction));
+    println!("sunset\t\t{}", time(report.sunset));
+    println!("moonset\t\t{}", time(report.moonset));
+    println!("best time\t{}", time(report.best_time));
+    println!("moon age\t{:.1} hours", report.age);
+    println!("lag\t\t{:.0} minutes", report.lag);
+    println!("elongation\t{:.2}°", report.elongation);
+    println!("arc of vision\t{:.2}°", report.arc_of_vision);
+    println!("azimuth diff\t{:.2}°", report.relative_azimuth);
+    println!("width\t\t{:.2}'", report.width);
+    println!(
+        "yallop\t\tq = {:.3}\tzone {:?}",
+        report.yallop.q, report.yallop.zone
+    );
+    println!(
+        "odeh\t\tV = {:.2}\tzone {:?}",
+        report.odeh.v, report.odeh.zone
+    );
+}
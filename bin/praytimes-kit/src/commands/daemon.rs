@@ -100,10 +100,7 @@ impl ExecutablePraytime {
                 .arg("-c")
                 .env("TYPE", format!("{:?}", self.praytime_type))
                 .env("DIFF", format!("{}", self.command.time_diff))
-                .env(
-                    "TIME",
-                    format!("{}", format_time(self.datetime, &format, &Local)),
-                )
+                .env("TIME", format_time(self.datetime, &format, &Local))
                 .arg(&self.command.cmd)
                 .spawn();
             match child {
@@ -142,13 +139,13 @@ impl Daemon {
     ) -> Vec<ExecutablePraytime> {
         self.commands
             .iter()
-            .map(|command| {
+            .flat_map(|command| {
                 praytimes
                     .iter()
                     .filter(|(praytime_type, _)| *praytime_type == command.praytime)
                     .map(|(praytime_type, datetime)| ExecutablePraytime {
                         command: command.clone(),
-                        praytime_type: praytime_type.clone(),
+                        praytime_type: *praytime_type,
                         datetime: *datetime,
                         execution_date: datetime.add(Duration::seconds(command.time_diff as i64)),
                     })
@@ -156,7 +153,6 @@ impl Daemon {
                     .collect::<Vec<_>>()
                     .into_iter()
             })
-            .flatten()
             .collect::<Vec<_>>()
     }
 }
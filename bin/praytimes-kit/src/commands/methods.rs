@@ -0,0 +1,30 @@
+use clap::Parser;
+use praytimes::methods::MethodRegistry;
+
+#[derive(Parser, Debug, Clone)]
+#[command(author, version, about, long_about = None)]
+pub struct Args {
+    /// whether to output as json format or not
+    #[arg(short, long, default_value_t = false)]
+    pub json: bool,
+}
+
+pub fn run(args: Args) {
+    let registry = MethodRegistry::default();
+
+    if args.json {
+        let methods: Vec<_> = registry.iter().collect();
+        let json = serde_json::to_string_pretty(&methods).unwrap();
+        println!("{json}");
+    } else {
+        for method in registry.iter() {
+            println!(
+                "{}\t{}\t{}\t{}",
+                method.aliases.first().unwrap_or(&method.id),
+                method.name,
+                method.region,
+                method.authority
+            );
+        }
+    }
+}
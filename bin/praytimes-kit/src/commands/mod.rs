@@ -1,4 +1,7 @@
 pub mod calculate;
+pub mod crescent;
 pub mod daemon;
+pub mod methods;
 pub mod serve;
 pub mod next;
+pub mod qibla;
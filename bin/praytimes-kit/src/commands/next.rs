@@ -43,18 +43,9 @@ pub fn run(args: Args) {
 
     let now = Utc::now().naive_utc();
     let cal = Calculator::new(conf.parameters.get_params(), conf.tune.unwrap_or_default());
-    let date = [
-        now.date().pred_opt().unwrap(),
-        now.date(),
-        now.date().succ_opt().unwrap(),
-    ]
-    .iter()
-    .map(|d| cal.calculate(&conf.location, &d).into_vec())
-    .flatten()
-    .skip_while(|(_, d)| d < &now)
-    .next();
-
-    if let Some((t, d)) = date {
+    let next = cal.current_and_next(&conf.location, &now).next;
+
+    if let Some((t, d)) = next {
         println!(
             "{t}: {}",
             format_time(d, &args.format.unwrap_or(conf.format), &Local)
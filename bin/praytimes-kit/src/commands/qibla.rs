@@ -0,0 +1,99 @@
+use std::{fmt::Display, path::PathBuf};
+
+use chrono::{Datelike, Local, TimeZone};
+use clap::Parser;
+use praytimes::{
+    qibla::{self, Qibla},
+    solar::Almanac,
+    types::{format_time, Location},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Parser, Debug, Clone)]
+#[command(author, version, about, long_about = None)]
+pub struct Args {
+    /// configuration file
+    #[arg(short, long)]
+    config: PathBuf,
+
+    /// year of the sun over kaaba dates ( default is this year )
+    #[arg(short, long, default_value_t = Local::now().year())]
+    pub year: i32,
+
+    /// strftime compatible format ( overwrites the config file's format field )
+    #[arg(short, long)]
+    pub format: Option<String>,
+
+    /// whether to output as json format or not
+    #[arg(short, long, default_value_t = false)]
+    pub json: bool,
+}
+
+fn default_format() -> String {
+    "%F %T".into()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Config {
+    location: Location,
+    #[serde(default = "default_format")]
+    format: String,
+}
+
+/// qibla of a location with the formatted instants of the sun over the kaaba
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QiblaOutput {
+    #[serde(flatten)]
+    pub qibla: Qibla,
+    pub sun_over_kaaba: Vec<String>,
+    pub sun_over_antipode: Vec<String>,
+}
+
+impl QiblaOutput {
+    pub fn new<TZ>(location: &Location, year: i32, format: &str, zone: &TZ) -> Self
+    where
+        TZ: TimeZone,
+        TZ::Offset: Display,
+    {
+        let (antipode, kaaba): (Vec<_>, Vec<_>) = qibla::sun_over_kaaba(year, &Almanac)
+            .into_iter()
+            .partition(|transit| transit.antipode);
+        let format_all = |transits: Vec<qibla::KaabaTransit>| {
+            transits
+                .into_iter()
+                .map(|transit| format_time(transit.instant, format, zone))
+                .collect()
+        };
+
+        Self {
+            qibla: Qibla::new(location),
+            sun_over_kaaba: format_all(kaaba),
+            sun_over_antipode: format_all(antipode),
+        }
+    }
+}
+
+pub fn run(args: Args) {
+    let conf = std::fs::read(args.config).expect("failed to open file");
+    let conf: Config = serde_json::from_slice(&conf).expect("failed to read configuration");
+
+    let output = QiblaOutput::new(
+        &conf.location,
+        args.year,
+        &args.format.unwrap_or(conf.format),
+        &Local,
+    );
+    if args.json {
+        let json = serde_json::to_string_pretty(&output).unwrap();
+        println!("{json}");
+    } else {
+        println!("direction\t{:.2}°", output.qibla.direction);
+        println!("distance\t{:.0} km", output.qibla.distance);
+        for instant in output.sun_over_kaaba {
+            println!("sun over kaaba\t{instant}");
+        }
+        for instant in output.sun_over_antipode {
+            println!("sun over antipode\t{instant}");
+        }
+    }
+}
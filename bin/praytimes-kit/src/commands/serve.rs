@@ -1,5 +1,5 @@
 use axum::http::StatusCode;
-use axum::routing::post;
+use axum::routing::{get, post};
 use axum::{Json, Router};
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use tracing::Level;
@@ -7,9 +7,12 @@ use tracing_subscriber::prelude::__tracing_subscriber_SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::EnvFilter;
 
-use chrono::{FixedOffset, Local, NaiveDate, Utc};
+use chrono::{Datelike, FixedOffset, Local, NaiveDate, Utc};
 use praytimes::{
+    error::CalcError,
+    methods::{Method, MethodRegistry},
     types::{FormattedTimes, Location, TuneOffsets},
+    zoned::Tz,
     Calculator,
 };
 
@@ -21,29 +24,93 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
 use crate::base::{CustomizableParams, Zone};
+use crate::commands::qibla::QiblaOutput;
 
 async fn calculate_handler(
     Json(payload): Json<CalculationInputs>,
 ) -> Result<Json<FormattedTimes>, (StatusCode, Json<Value>)> {
-    let result = Calculator::new(
-        payload.parameters.get_params(),
-        payload.tune.unwrap_or_default(),
-    )
-    .calculate(&payload.location, &payload.date);
-
-    let formatted = match payload.zone {
-        Zone::Local => result.format_times(&payload.format, &Local),
-        Zone::Utc => result.format_times(&payload.format, &Utc),
-        Zone::Fixed(o) => result.format_times(
+    let invalid_input = |error: CalcError| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": error.to_string()})),
+        )
+    };
+    payload.location.validate().map_err(invalid_input)?;
+    let params = payload
+        .parameters
+        .get_params()
+        .validated()
+        .map_err(invalid_input)?;
+    let calculator = Calculator::new(params, payload.tune.unwrap_or_default());
+    let times = || calculator.calculate(&payload.location, &payload.date);
+    let invalid_zone = || {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error":"invalid time zone"})),
+        )
+    };
+
+    let formatted = match &payload.zone {
+        Zone::Iana(name) => {
+            let zone: Tz = name.parse().map_err(|_| invalid_zone())?;
+            calculator
+                .calculate_in(&payload.location, &payload.date, &zone)
+                .format_times(&payload.format)
+        }
+        Zone::Local => times().format_times(&payload.format, &Local),
+        Zone::Utc => times().format_times(&payload.format, &Utc),
+        Zone::Fixed(o) => {
+            let offset = FixedOffset::east_opt(*o).ok_or_else(invalid_zone)?;
+            calculator
+                .clone()
+                .with_utc_offset(offset)
+                .calculate(&payload.location, &payload.date)
+                .format_times(&payload.format, &offset)
+        }
+    };
+
+    Ok(Json(formatted))
+}
+
+async fn qibla_handler(
+    Json(payload): Json<QiblaInputs>,
+) -> Result<Json<QiblaOutput>, (StatusCode, Json<Value>)> {
+    payload.location.validate().map_err(|error| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": error.to_string()})),
+        )
+    })?;
+    let invalid_zone = || {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error":"invalid time zone"})),
+        )
+    };
+    let year = payload.year.unwrap_or_else(|| Local::now().year());
+
+    let output = match &payload.zone {
+        Zone::Local => QiblaOutput::new(&payload.location, year, &payload.format, &Local),
+        Zone::Utc => QiblaOutput::new(&payload.location, year, &payload.format, &Utc),
+        Zone::Fixed(o) => QiblaOutput::new(
+            &payload.location,
+            year,
+            &payload.format,
+            &FixedOffset::east_opt(*o).ok_or_else(invalid_zone)?,
+        ),
+        Zone::Iana(name) => QiblaOutput::new(
+            &payload.location,
+            year,
             &payload.format,
-            &FixedOffset::east_opt(o).ok_or((
-                StatusCode::BAD_REQUEST,
-                Json(json!({"error":"invalid time zone"})),
-            ))?,
+            &name.parse::<Tz>().map_err(|_| invalid_zone())?,
         ),
     };
 
-    Ok(Json(formatted))
+    Ok(Json(output))
+}
+
+async fn methods_handler() -> Json<Vec<Method>> {
+    Json(MethodRegistry::default().iter().cloned().collect())
 }
 
 pub async fn serve() {
@@ -53,6 +120,8 @@ pub async fn serve() {
         .init();
     let app = Router::new()
         .route("/calculate", post(calculate_handler))
+        .route("/qibla", post(qibla_handler))
+        .route("/methods", get(methods_handler))
         .layer(
             TraceLayer::new_for_http()
                 .make_span_with(trace::DefaultMakeSpan::new().level(Level::INFO))
@@ -95,3 +164,14 @@ pub struct CalculationInputs {
     #[serde(default = "default_timezone")]
     pub zone: Zone,
 }
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QiblaInputs {
+    #[serde(default = "default_format")]
+    pub format: String,
+    pub location: Location,
+    /// year of the sun over kaaba dates ( default is this year )
+    pub year: Option<i32>,
+    #[serde(default = "default_timezone")]
+    pub zone: Zone,
+}
@@ -18,7 +18,16 @@ pub enum SubCommands {
     Daemon(commands::daemon::Args),
 
     /// get next praytime event 
-    Next(commands::next::Args)
+    Next(commands::next::Args),
+
+    /// qibla direction and distance, and when the sun is over the kaaba
+    Qibla(commands::qibla::Args),
+
+    /// crescent visibility report of an evening ( Yallop and Odeh criteria )
+    Crescent(commands::crescent::Args),
+
+    /// list the calculation methods
+    Methods(commands::methods::Args),
 }
 
 pub async fn run(args: Args) {
@@ -27,5 +36,8 @@ pub async fn run(args: Args) {
         SubCommands::Calculate(c) => commands::calculate::run(c),
         SubCommands::Daemon(d) => commands::daemon::run(d).await,
         SubCommands::Next(n) => commands::next::run(n),
+        SubCommands::Qibla(q) => commands::qibla::run(q),
+        SubCommands::Crescent(c) => commands::crescent::run(c),
+        SubCommands::Methods(m) => commands::methods::run(m),
     }
 }
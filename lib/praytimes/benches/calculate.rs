@@ -0,0 +1,82 @@
+use chrono::NaiveDate;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use praytimes::{
+    methods,
+    types::{HighLatsMethod, Location, Parameters, Refinement},
+    Calculator,
+};
+
+fn location(latitude: f64, longitude: f64) -> Location {
+    Location {
+        latitude,
+        longitude,
+        ..Default::default()
+    }
+}
+
+/// cost of the calculation of a day
+fn day(c: &mut Criterion) {
+    let tehran = location(35.7, 51.4);
+    let oslo = location(59.9, 10.75);
+    let date = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+    let converge = Refinement::Converge {
+        tolerance: 1.0,
+        max_iterations: 10,
+    };
+
+    let cases = [
+        (
+            "praytimes.js",
+            Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default()),
+            &tehran,
+        ),
+        (
+            "converge",
+            Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default())
+                .with_refinement(converge),
+            &tehran,
+        ),
+        (
+            "moonsighting committee",
+            Calculator::new(
+                methods::MOONSIGHTING_COMMITTEE_WORLDWIDE,
+                Default::default(),
+            ),
+            &tehran,
+        ),
+        (
+            "high latitude",
+            Calculator::new(
+                Parameters {
+                    high_latitudes: HighLatsMethod::AngleBased,
+                    ..methods::MUSLIM_WORLD_LEAGUE
+                },
+                Default::default(),
+            )
+            .with_refinement(converge),
+            &oslo,
+        ),
+        (
+            "nearest day",
+            Calculator::new(
+                Parameters {
+                    high_latitudes: HighLatsMethod::NearestDay,
+                    ..methods::MUSLIM_WORLD_LEAGUE
+                },
+                Default::default(),
+            ),
+            &oslo,
+        ),
+    ];
+
+    let mut group = c.benchmark_group("day");
+    for (name, calculator, location) in cases {
+        group.bench_function(name, |b| {
+            b.iter(|| calculator.calculate(black_box(location), black_box(&date)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, day);
+criterion_main!(benches);
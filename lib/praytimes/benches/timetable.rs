@@ -0,0 +1,79 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use praytimes::{
+    methods,
+    timetable::DateRange,
+    types::{Location, Refinement},
+    Calculator,
+};
+
+fn location(latitude: f64, longitude: f64) -> Location {
+    Location {
+        latitude,
+        longitude,
+        ..Default::default()
+    }
+}
+
+fn calculator() -> Calculator {
+    Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default()).with_refinement(
+        Refinement::Converge {
+            tolerance: 1.0,
+            max_iterations: 10,
+        },
+    )
+}
+
+/// a year of a location, day by day and as a timetable, with the default refinement and converging
+fn year(c: &mut Criterion) {
+    let tehran = location(35.7, 51.4);
+    let range = DateRange::year(2024).unwrap();
+    let cases = [
+        (
+            "default",
+            Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default()),
+        ),
+        ("converge", calculator()),
+    ];
+
+    let mut group = c.benchmark_group("year");
+    for (name, calculator) in cases {
+        group.bench_function(format!("calculate {name}"), |b| {
+            b.iter(|| {
+                range
+                    .days()
+                    .map(|date| calculator.calculate(black_box(&tehran), &date))
+                    .collect::<Vec<_>>()
+            })
+        });
+        group.bench_function(format!("timetable {name}"), |b| {
+            b.iter(|| calculator.timetable(black_box(&tehran), &range))
+        });
+    }
+    group.finish();
+}
+
+/// a month of many locations, one after the other and with `timetables` ( parallel with the `rayon` feature )
+fn locations(c: &mut Criterion) {
+    let calculator = calculator();
+    let locations: Vec<_> = (0..64)
+        .map(|i| location(-60.0 + i as f64 * 2.0, -180.0 + i as f64 * 5.6))
+        .collect();
+    let range = DateRange::month(2024, 6).unwrap();
+
+    let mut group = c.benchmark_group("locations");
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            locations
+                .iter()
+                .map(|location| calculator.timetable(black_box(location), &range))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("timetables", |b| {
+        b.iter(|| calculator.timetables(black_box(&locations), &range))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, year, locations);
+criterion_main!(benches);
@@ -1,26 +1,449 @@
-use crate::internal_calculator;
-use crate::types::{Location, Parameters, PraytimesOutput, TuneOffsets};
-use crate::utils::julian_date::to_julian_date;
-use chrono::{NaiveDate, NaiveDateTime};
+use crate::current::{self, CurrentAndNext};
+use crate::error::CalcError;
+use crate::forbidden::{self, ForbiddenInterval, ForbiddenKind, ForbiddenRules};
+use crate::hijri::{HijriCalendar, HijriDate};
+use crate::internal_calculator::{DayCache, Estimates, InternalCalculator};
+use crate::solar::{self, Almanac, SolarModel, SunCoordinates};
+use crate::timetable::{DateRange, Timetable, TimetableRow};
+use crate::types::{
+    Ihtiyat, Location, NoonSide, Parameters, PraytimesHours, PraytimesOutput, Refinement, Rounding,
+    RoundingPolicy, TuneOffsets,
+};
+use crate::utils::julian_date::date_from_julian;
+use crate::voluntary::{self, VoluntaryTimes};
+use crate::windows::{self, PrayerWindows, WindowBounds, WindowRules};
+use alloc::{borrow::Cow, vec::Vec};
+use chrono::{FixedOffset, NaiveDate, NaiveDateTime, Offset, Utc};
+
+// the solar model is shared by the clones of the calculator, `Rc` in `no_std` where the targets can miss atomic
+// pointers ( like thumbv6m )
+#[cfg(feature = "std")]
+type Shared<T> = alloc::sync::Arc<T>;
+#[cfg(not(feature = "std"))]
+type Shared<T> = alloc::rc::Rc<T>;
 
 /// Main public api for the prayertimes calculation
 #[derive(Debug, Clone)]
 pub struct Calculator {
     params: Parameters,
     tuning_offsets: TuneOffsets,
+    hijri: Option<HijriCalendar>,
+    solar_model: Shared<dyn SolarModel>,
+    refinement: Refinement,
+    utc_offset: FixedOffset,
 }
 
 impl Calculator {
     /// Calculate the prayertimes in a given location and a given date
     pub fn calculate(&self, location: &Location, date: &NaiveDate) -> PraytimesOutput {
-        let calculator = internal_calculator::InternalCalculator {
-            date: &date,
-            location: &location,
-            params: &self.params,
-            julian_date: to_julian_date(date, location),
+        self.calculate_from(
+            location,
+            date,
+            self.hijri_of(date, None),
+            &self.day_cache(),
+            |_| self.utc_offset,
+        )
+    }
+
+    /// Calculate the prayertimes of the day containing a julian day, in fractional utc hours since 0h of that day
+    ///
+    /// the times are the same as [`Calculator::calculate`] with the ihtiyat and the tuning offsets, but without the
+    /// rounding and the hijri date. `None` when the julian day is out of the range of the dates
+    pub fn calculate_hours(&self, location: &Location, julian_day: f64) -> Option<PraytimesHours> {
+        let date = date_from_julian(julian_day)?;
+        let params = self.params_of(&date);
+        let cache = self.day_cache();
+        let hours = self
+            .refined_calculator(location, &date, &params, &cache)
+            .hours();
+        let ihtiyat = &params.ihtiyat;
+        let tuning = &self.tuning_offsets;
+        let offset = |hours: Option<f64>, ihtiyat: f64, tuning: Option<f64>| {
+            hours.map(|hours| hours + (ihtiyat + tuning.unwrap_or(0.0)) / 60.0)
+        };
+        Some(PraytimesHours {
+            imsak: offset(hours.imsak, ihtiyat.imsak, tuning.imsak),
+            fajr: offset(hours.fajr, ihtiyat.fajr, tuning.fajr),
+            sunrise: offset(hours.sunrise, ihtiyat.sunrise, tuning.sunrise),
+            dhuhr: offset(hours.dhuhr, ihtiyat.dhuhr, tuning.dhuhr),
+            asr: offset(hours.asr, ihtiyat.asr, tuning.asr),
+            sunset: offset(hours.sunset, ihtiyat.sunset, tuning.sunset),
+            maghrib: offset(hours.maghrib, ihtiyat.maghrib, tuning.maghrib),
+            isha: offset(hours.isha, ihtiyat.isha, tuning.isha),
+            midnight: offset(hours.midnight, ihtiyat.midnight, tuning.midnight),
+            adjusted: hours.adjusted,
+        })
+    }
+
+    /// The prayertimes of a location for a range of days, see [`crate::timetable`]
+    ///
+    /// the rows are the same as [`Calculator::calculate`] for each day, the sun positions of the days and the
+    /// nearest days of [`crate::types::HighLatsMethod::NearestDay`] are computed once for the whole timetable
+    pub fn timetable(&self, location: &Location, range: &DateRange) -> Timetable {
+        let cache = self.day_cache();
+        let mut previous = None;
+        let rows = range
+            .days()
+            .map(|date| {
+                let hijri = self.hijri_of(&date, previous);
+                previous = hijri.map(|hijri| (date, hijri));
+                TimetableRow {
+                    date,
+                    times: self.calculate_from(location, &date, hijri, &cache, |_| self.utc_offset),
+                }
+            })
+            .collect();
+        Timetable {
+            location: location.clone(),
+            rows,
+        }
+    }
+
+    /// The timetables of many locations for a range of days, like [`Calculator::timetable`]
+    ///
+    /// the locations are calculated in parallel with the `rayon` feature
+    pub fn timetables(&self, locations: &[Location], range: &DateRange) -> Vec<Timetable> {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            locations
+                .par_iter()
+                .map(|location| self.timetable(location, range))
+                .collect()
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            locations
+                .iter()
+                .map(|location| self.timetable(location, range))
+                .collect()
+        }
+    }
+
+    /// the prayertimes of a day in a hijri date ( see [`Calculator::hijri_of`] ), with the solar quantities of a
+    /// cache of the location
+    pub(crate) fn calculate_from(
+        &self,
+        location: &Location,
+        date: &NaiveDate,
+        hijri: Option<HijriDate>,
+        cache: &DayCache,
+        offset: impl Fn(&NaiveDateTime) -> FixedOffset,
+    ) -> PraytimesOutput {
+        let params = self.params_for(hijri);
+        let times = self
+            .refined_calculator(location, date, &params, cache)
+            .calculate();
+        PraytimesOutput {
+            hijri: self.hijri.and(hijri),
+            ..Self::round(
+                self.tune(Self::add_ihtiyat(times, &params.ihtiyat)),
+                &params.rounding,
+                offset,
+            )
+        }
+    }
+
+    /// internal calculator of a day with the estimates refined as the refinement says
+    fn refined_calculator<'a>(
+        &'a self,
+        location: &'a Location,
+        date: &'a NaiveDate,
+        params: &'a Parameters,
+        cache: &'a DayCache<'a>,
+    ) -> InternalCalculator<'a> {
+        let calculator =
+            InternalCalculator::cached(params, location, date, Estimates::default(), cache);
+        self.refine(
+            calculator,
+            |calculator| calculator.with_estimates(calculator.refined_estimates()),
+            |a, b| a.estimates.difference(&b.estimates),
+        )
+    }
+
+    /// the solar quantities shared by the calculations of a location
+    pub(crate) fn day_cache(&self) -> DayCache<'_> {
+        DayCache::new(self.solar_model.as_ref())
+    }
+
+    /// parameters of a day, with the hijri rules of its month applied
+    fn params_of(&self, date: &NaiveDate) -> Cow<'_, Parameters> {
+        if self.params.hijri_rules.is_empty() {
+            return Cow::Borrowed(&self.params);
+        }
+        self.params_for(self.hijri.unwrap_or_default().to_hijri(date))
+    }
+
+    /// parameters of a day in a hijri date, with the hijri rules of its month applied
+    fn params_for(&self, hijri: Option<HijriDate>) -> Cow<'_, Parameters> {
+        match hijri {
+            Some(hijri) if !self.params.hijri_rules.is_empty() => {
+                Cow::Owned(self.params.for_hijri_month(hijri.month))
+            }
+            _ => Cow::Borrowed(&self.params),
+        }
+    }
+
+    /// hijri date of a day for the output and the hijri rules, converted once per day and `None` when neither needs
+    /// it
+    ///
+    /// the day after a `previous` day before the 29th of its month is the next day of the month, without converting
+    pub(crate) fn hijri_of(
+        &self,
+        date: &NaiveDate,
+        previous: Option<(NaiveDate, HijriDate)>,
+    ) -> Option<HijriDate> {
+        if self.hijri.is_none() && self.params.hijri_rules.is_empty() {
+            return None;
+        }
+        match previous {
+            Some((day, hijri)) if day.succ_opt() == Some(*date) && hijri.day < 29 => {
+                Some(HijriDate {
+                    day: hijri.day + 1,
+                    ..hijri
+                })
+            }
+            _ => self.hijri.unwrap_or_default().to_hijri(date),
+        }
+    }
+
+    /// repeat a step improving the estimates, as many times as the refinement says
+    fn refine<T>(
+        &self,
+        mut estimates: T,
+        step: impl Fn(&T) -> T,
+        difference: impl Fn(&T, &T) -> f64,
+    ) -> T {
+        match self.refinement {
+            Refinement::Iterations(iterations) => {
+                for _ in 0..iterations {
+                    estimates = step(&estimates);
+                }
+            }
+            Refinement::Converge {
+                tolerance,
+                max_iterations,
+            } => {
+                for _ in 0..max_iterations {
+                    let refined = step(&estimates);
+                    let moved = difference(&refined, &estimates);
+                    estimates = refined;
+                    if moved < tolerance {
+                        break;
+                    }
+                }
+            }
+        }
+        estimates
+    }
+
+    /// The instant when the shadow of an object is its noon shadow plus `factor` times its length
+    ///
+    /// `1.0` after noon is the asr of the majority and `2.0` the hanafi asr, the refinement of the calculator
+    /// is used but not the tuning offsets
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use praytimes::{methods, types::{Location, NoonSide}, Calculator};
+    ///
+    /// let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    /// let location = Location { latitude: 35.7, longitude: 51.4, ..Default::default() };
+    /// let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    ///
+    /// let [shafii, hanafi] = [1.0, 2.0].map(|factor| calculator.shadow_time(&location, &date, factor, NoonSide::AfterNoon));
+    /// assert!(shafii < hanafi);
+    /// assert_eq!(shafii, calculator.calculate(&location, &date).asr);
+    /// ```
+    pub fn shadow_time(
+        &self,
+        location: &Location,
+        date: &NaiveDate,
+        factor: f64,
+        side: NoonSide,
+    ) -> Option<NaiveDateTime> {
+        let cache = self.day_cache();
+        let calculator =
+            InternalCalculator::cached(&self.params, location, date, Estimates::default(), &cache);
+        let guess = match side {
+            NoonSide::BeforeNoon => 11.0 / 24.0,
+            NoonSide::AfterNoon => calculator.estimates.asr,
         };
-        let times = calculator.calculate();
-        self.tune(times)
+        let to_estimate = |hours: f64| (hours + location.longitude / 15.0) / 24.0;
+
+        let estimate = self.refine(
+            guess,
+            |estimate| {
+                let refined = to_estimate(calculator.shadow_time(factor, *estimate, side));
+                if refined.is_finite() {
+                    refined
+                } else {
+                    *estimate
+                }
+            },
+            |a, b| (a - b).abs() * 86400.0,
+        );
+        calculator.datetime_from_hours(calculator.shadow_time(factor, estimate, side))
+    }
+
+    /// The intervals of a day when voluntary prayers are disliked ( makruh ), sorted
+    ///
+    /// they are based on the astronomical sunrise, transit and sunset, the ihtiyat, tuning and rounding of the
+    /// calculator are not used, and the intervals that don't exist ( without a sunrise in high latitudes ) are skipped
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use praytimes::{forbidden::{ForbiddenKind, ForbiddenRules}, methods, types::Location, Calculator};
+    ///
+    /// let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    /// let location = Location { latitude: 35.7, longitude: 51.4, ..Default::default() };
+    /// let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    ///
+    /// let intervals = calculator.forbidden_intervals(&location, &date, &ForbiddenRules::default());
+    /// assert_eq!(intervals.len(), 3);
+    /// assert_eq!(intervals[1].kind, ForbiddenKind::Zawal);
+    /// ```
+    pub fn forbidden_intervals(
+        &self,
+        location: &Location,
+        date: &NaiveDate,
+        rules: &ForbiddenRules,
+    ) -> Vec<ForbiddenInterval> {
+        let params = self.params_of(date);
+        let cache = self.day_cache();
+        let calculator = self.refined_calculator(location, date, &params, &cache);
+        forbidden::intervals(&calculator, rules)
+    }
+
+    /// The times of the voluntary prayers and the divisions of the night of a day
+    ///
+    /// ishraq and duha follow the forbidden intervals of the rules ( see [`Calculator::forbidden_intervals`] ), and
+    /// the night follows the midnight method of the parameters
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use praytimes::{forbidden::ForbiddenRules, methods, types::Location, Calculator};
+    ///
+    /// let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    /// let location = Location { latitude: 35.7, longitude: 51.4, ..Default::default() };
+    /// let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    ///
+    /// let times = calculator.voluntary_times(&location, &date, &ForbiddenRules::default());
+    /// assert!(times.ishraq < times.duha_start);
+    /// assert!(times.first_third < times.last_third);
+    /// ```
+    pub fn voluntary_times(
+        &self,
+        location: &Location,
+        date: &NaiveDate,
+        rules: &ForbiddenRules,
+    ) -> VoluntaryTimes {
+        let params = self.params_of(date);
+        let cache = self.day_cache();
+        let calculator = self.refined_calculator(location, date, &params, &cache);
+        voluntary::times(&calculator, rules)
+    }
+
+    /// The start and end of the obligatory prayers of a day
+    ///
+    /// the windows are made of the calculated times ( with the ihtiyat, tuning and rounding ), isha can end at the
+    /// fajr of the next day
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use praytimes::{methods, types::Location, windows::{Madhab, WindowRules}, Calculator};
+    ///
+    /// let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    /// let location = Location { latitude: 35.7, longitude: 51.4, ..Default::default() };
+    /// let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    ///
+    /// let rules = WindowRules { madhab: Some(Madhab::Shafii), ..Default::default() };
+    /// let windows = calculator.prayer_windows(&location, &date, &rules);
+    /// assert_eq!(windows.dhuhr.end, windows.asr.start);
+    /// assert!(windows.asr.preferred_end < windows.asr.end);
+    /// ```
+    pub fn prayer_windows(
+        &self,
+        location: &Location,
+        date: &NaiveDate,
+        rules: &WindowRules,
+    ) -> PrayerWindows {
+        let times = self.calculate(location, date);
+        let forbidden_rules = ForbiddenRules {
+            before_sunset: rules.yellowing,
+            ..Default::default()
+        };
+        let yellowing = self
+            .forbidden_intervals(location, date, &forbidden_rules)
+            .into_iter()
+            .find(|interval| interval.kind == ForbiddenKind::BeforeSunset)
+            .map(|interval| interval.start);
+
+        let bounds = WindowBounds {
+            next_fajr: date
+                .succ_opt()
+                .and_then(|next| self.calculate(location, &next).fajr),
+            double_shadow: self.shadow_time(location, date, 2.0, NoonSide::AfterNoon),
+            yellowing,
+            first_third: self
+                .voluntary_times(location, date, &forbidden_rules)
+                .first_third,
+        };
+        windows::windows(&times, &bounds, rules)
+    }
+
+    /// The current prayer of a utc instant with its window, the next event and the time until it
+    ///
+    /// the current prayer is one of the obligatory prayers, from its time to the end of its window with the default
+    /// [`WindowRules`] ( see [`Calculator::prayer_windows`] ), and the next event is the first event at or after the
+    /// instant. the events of the day before and after are included, so the isha window ends at the next day's fajr,
+    /// and the events missing in high latitudes are skipped
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use praytimes::{methods, types::{Location, PraytimeType}, Calculator};
+    ///
+    /// let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    /// let location = Location { latitude: 35.7, longitude: 51.4, ..Default::default() };
+    /// let instant = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap().and_hms_opt(10, 0, 0).unwrap();
+    ///
+    /// let now = calculator.current_and_next(&location, &instant);
+    /// let (prayer, window) = now.current.unwrap();
+    /// assert_eq!(prayer, PraytimeType::Dhuhr);
+    /// assert_eq!(now.next, Some((PraytimeType::Asr, window.end.unwrap())));
+    /// assert!(now.remaining().unwrap().num_minutes() > 0);
+    /// ```
+    pub fn current_and_next(&self, location: &Location, instant: &NaiveDateTime) -> CurrentAndNext {
+        let date = instant.date();
+        let days: Vec<_> = [date.pred_opt(), Some(date), date.succ_opt()]
+            .into_iter()
+            .flatten()
+            .map(|date| self.calculate(location, &date))
+            .collect();
+        current::current_and_next(&days, *instant)
+    }
+
+    /// Calculate the prayertimes like [`Calculator::calculate`], after checking the location and the parameters
+    ///
+    /// invalid inputs ( like a latitude of 200° or a negative elevation ) make the times silently `None` in
+    /// [`Calculator::calculate`], here they are reported instead
+    pub fn try_calculate(
+        &self,
+        location: &Location,
+        date: &NaiveDate,
+    ) -> Result<PraytimesOutput, CalcError> {
+        location.validate()?;
+        self.params.validate()?;
+        Ok(self.calculate(location, date))
+    }
+
+    /// Position of the sun in the sky of a location at a utc instant, with the solar model of the calculation
+    pub fn sun_coordinates(&self, location: &Location, time: &NaiveDateTime) -> SunCoordinates {
+        solar::coordinates(self.solar_model.as_ref(), location, time)
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    pub(crate) fn hijri_date(&self, date: &NaiveDate) -> Option<HijriDate> {
+        self.hijri.and_then(|calendar| calendar.to_hijri(date))
     }
     fn tune(&self, times: PraytimesOutput) -> PraytimesOutput {
         PraytimesOutput {
@@ -33,6 +456,43 @@ impl Calculator {
             isha: Self::tune_time(times.isha, self.tuning_offsets.isha),
             midnight: Self::tune_time(times.midnight, self.tuning_offsets.midnight),
             sunrise: Self::tune_time(times.sunrise, self.tuning_offsets.sunrise),
+            ..times
+        }
+    }
+    fn add_ihtiyat(times: PraytimesOutput, ihtiyat: &Ihtiyat) -> PraytimesOutput {
+        PraytimesOutput {
+            imsak: Self::tune_time(times.imsak, Some(ihtiyat.imsak)),
+            fajr: Self::tune_time(times.fajr, Some(ihtiyat.fajr)),
+            dhuhr: Self::tune_time(times.dhuhr, Some(ihtiyat.dhuhr)),
+            asr: Self::tune_time(times.asr, Some(ihtiyat.asr)),
+            sunset: Self::tune_time(times.sunset, Some(ihtiyat.sunset)),
+            maghrib: Self::tune_time(times.maghrib, Some(ihtiyat.maghrib)),
+            isha: Self::tune_time(times.isha, Some(ihtiyat.isha)),
+            midnight: Self::tune_time(times.midnight, Some(ihtiyat.midnight)),
+            sunrise: Self::tune_time(times.sunrise, Some(ihtiyat.sunrise)),
+            ..times
+        }
+    }
+    /// round the times on the minutes of the utc offset of each time
+    fn round(
+        times: PraytimesOutput,
+        rounding: &RoundingPolicy,
+        offset: impl Fn(&NaiveDateTime) -> FixedOffset,
+    ) -> PraytimesOutput {
+        let round = |time: Option<NaiveDateTime>, rounding: Rounding| {
+            time.map(|time| rounding.round_in(time, offset(&time)))
+        };
+        PraytimesOutput {
+            imsak: round(times.imsak, rounding.imsak),
+            fajr: round(times.fajr, rounding.fajr),
+            dhuhr: round(times.dhuhr, rounding.dhuhr),
+            asr: round(times.asr, rounding.asr),
+            sunset: round(times.sunset, rounding.sunset),
+            maghrib: round(times.maghrib, rounding.maghrib),
+            isha: round(times.isha, rounding.isha),
+            midnight: round(times.midnight, rounding.midnight),
+            sunrise: round(times.sunrise, rounding.sunrise),
+            ..times
         }
     }
     fn tune_time(time: Option<NaiveDateTime>, offset: Option<f64>) -> Option<NaiveDateTime> {
@@ -45,10 +505,58 @@ impl Calculator {
     }
     /// Create new calculator based on input parameters ( can be grabbed from praytimes::methods as well or manually used)
     /// There is also a tuning offsets input so you can specify how the times are get tuned after calculation for precaution
+    /// ( on top of the ihtiyat of the parameters, see [`Parameters::ihtiyat`] )
     pub fn new(params: Parameters, tuning_offsets: TuneOffsets) -> Self {
         Self {
             params,
             tuning_offsets,
+            hijri: None,
+            solar_model: Shared::new(Almanac),
+            refinement: Refinement::default(),
+            utc_offset: Utc.fix(),
+        }
+    }
+
+    /// Also convert the calculated day to the given hijri calendar ( see [`PraytimesOutput::hijri`] )
+    pub fn with_hijri_calendar(self, calendar: HijriCalendar) -> Self {
+        Self {
+            hijri: Some(calendar),
+            ..self
+        }
+    }
+
+    /// Use another solar ephemeris for the calculation ( default is [`Almanac`] )
+    ///
+    /// the model is shared by the clones of the calculator, in an `Arc` with `std` and an `Rc` without it
+    pub fn with_solar_model(self, solar_model: impl SolarModel + 'static) -> Self {
+        Self {
+            solar_model: Shared::new(solar_model),
+            ..self
+        }
+    }
+
+    /// Recompute the events with the sun position at their previous result ( default is no refinement, like PrayTimes.js )
+    pub fn with_refinement(self, refinement: Refinement) -> Self {
+        Self { refinement, ..self }
+    }
+
+    /// Round the times on the minutes of a local utc offset ( default is utc, see [`Rounding::round_in`] )
+    ///
+    /// the times stay in utc, only the rounding uses the offset, like the `Minutes(5)` of UTC+5:45 landing on the
+    /// multiples of 5 minutes of the local clock. `Calculator::calculate_in` ( `chrono-tz` feature ) rounds in the offsets
+    /// of its zone instead
+    pub fn with_utc_offset(self, utc_offset: FixedOffset) -> Self {
+        Self { utc_offset, ..self }
+    }
+
+    /// Replace the rounding of the parameters ( see [`Parameters::rounding`] )
+    pub fn with_rounding(self, rounding: RoundingPolicy) -> Self {
+        Self {
+            params: Parameters {
+                rounding,
+                ..self.params
+            },
+            ..self
         }
     }
 }
@@ -0,0 +1,73 @@
+//! The prayer time of an instant
+//!
+//! the current prayer is the obligatory prayer whose window ( see [`crate::windows`] with the default
+//! [`WindowRules`] ) contains the instant, and the next event is the first event at or after it. the events of the
+//! days around the instant are searched so the windows can cross midnight and skip the events missing in high
+//! latitudes. it's calculated with [`crate::Calculator::current_and_next`].
+use alloc::vec::Vec;
+
+use chrono::{Duration, NaiveDateTime};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::{PraytimeType, PraytimesOutput};
+use crate::windows::{self, Window, WindowBounds, WindowRules};
+
+/// the current prayer and next event of an instant, in utc
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct CurrentAndNext {
+    pub instant: NaiveDateTime,
+    /// the obligatory prayer whose window starts at or before the instant and ends after it, `None` between the
+    /// windows ( like from sunrise to dhuhr ) or when the end of a window is missing in high latitudes
+    pub current: Option<(PraytimeType, Window)>,
+    /// the first event at or after the instant, of any type
+    pub next: Option<(PraytimeType, NaiveDateTime)>,
+}
+
+impl CurrentAndNext {
+    /// time until the next event
+    pub fn remaining(&self) -> Option<Duration> {
+        self.next.map(|(_, time)| time - self.instant)
+    }
+}
+
+/// the current prayer and next event among the times of consecutive days
+pub(crate) fn current_and_next(days: &[PraytimesOutput], instant: NaiveDateTime) -> CurrentAndNext {
+    let contains = |window: &Window| match (window.start, window.end) {
+        (Some(start), Some(end)) => start <= instant && instant < end,
+        _ => false,
+    };
+    // isha ends at the fajr of the next day, unknown for the last day
+    let current = days
+        .iter()
+        .enumerate()
+        .flat_map(|(i, times)| {
+            let bounds = WindowBounds {
+                next_fajr: days.get(i + 1).and_then(|next| next.fajr),
+                double_shadow: None,
+                yellowing: None,
+                first_third: None,
+            };
+            let windows = windows::windows(times, &bounds, &WindowRules::default());
+            [
+                (PraytimeType::Fajr, windows.fajr),
+                (PraytimeType::Dhuhr, windows.dhuhr),
+                (PraytimeType::Asr, windows.asr),
+                (PraytimeType::Maghrib, windows.maghrib),
+                (PraytimeType::Isha, windows.isha),
+            ]
+        })
+        .find(|(_, window)| contains(window));
+
+    // the events of a day can be out of order after tuning, or overlap the next day
+    let mut events: Vec<_> = days.iter().flat_map(|times| times.into_vec()).collect();
+    events.sort_by_key(|(_, time)| *time);
+
+    CurrentAndNext {
+        instant,
+        current,
+        next: events.into_iter().find(|(_, time)| *time >= instant),
+    }
+}
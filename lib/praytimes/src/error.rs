@@ -0,0 +1,71 @@
+//! Errors of the fallible calculation api ( see [`crate::Calculator::try_calculate`] )
+use core::fmt;
+
+use crate::types::PraytimeType;
+
+/// reason why a [`Location`](crate::types::Location) or [`Parameters`](crate::types::Parameters) can't be used for the calculation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CalcError {
+    /// latitude is not a number between -90 and 90
+    InvalidLatitude(f64),
+
+    /// longitude is not a number between -180 and 180
+    InvalidLongitude(f64),
+
+    /// elevation is negative or not a number
+    InvalidElevation(f64),
+
+    /// temperature of the atmosphere is below the absolute zero or not a number
+    InvalidTemperature(f64),
+
+    /// pressure of the atmosphere is not a positive number
+    InvalidPressure(f64),
+
+    /// sun angle of an event is not a number between 0 and 90
+    InvalidAngle { event: PraytimeType, degree: f64 },
+
+    /// minutes of an event are not a finite number
+    InvalidMinutes { event: PraytimeType, minutes: f64 },
+
+    /// asr shadow factor is not a positive number
+    InvalidAsrFactor(f64),
+
+    /// hijri rule for a month out of 1 to 12, or for an event without a calculation unit
+    InvalidHijriRule { month: u32, event: PraytimeType },
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalcError::InvalidLatitude(latitude) => {
+                write!(f, "latitude {latitude} is not between -90 and 90")
+            }
+            CalcError::InvalidLongitude(longitude) => {
+                write!(f, "longitude {longitude} is not between -180 and 180")
+            }
+            CalcError::InvalidElevation(elevation) => {
+                write!(f, "elevation {elevation} is not a positive number")
+            }
+            CalcError::InvalidTemperature(temperature) => {
+                write!(f, "temperature {temperature} is below the absolute zero")
+            }
+            CalcError::InvalidPressure(pressure) => {
+                write!(f, "pressure {pressure} is not a positive number")
+            }
+            CalcError::InvalidAngle { event, degree } => {
+                write!(f, "{event} angle {degree} is not between 0 and 90")
+            }
+            CalcError::InvalidMinutes { event, minutes } => {
+                write!(f, "{event} minutes {minutes} is not a finite number")
+            }
+            CalcError::InvalidAsrFactor(factor) => {
+                write!(f, "asr factor {factor} is not a positive number")
+            }
+            CalcError::InvalidHijriRule { month, event } => {
+                write!(f, "{event} can't be changed in the hijri month {month}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for CalcError {}
@@ -0,0 +1,116 @@
+//! Intervals when voluntary prayers are disliked ( makruh )
+//!
+//! three intervals of the day are forbidden: from sunrise until the sun has risen a spear's length, the zawal
+//! when the sun is at its highest, and before sunset. their length is described by [`ForbiddenRules`] and they
+//! are calculated with [`crate::Calculator::forbidden_intervals`].
+use alloc::vec::Vec;
+
+use chrono::NaiveDateTime;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::internal_calculator::InternalCalculator;
+use crate::types::{Degrees, Minutes, NoonSide};
+
+/// end of the interval after sunrise, or start of the interval before sunset
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ForbiddenLimit {
+    /// apparent altitude of the sun above the horizon
+    Altitude(Degrees),
+    /// minutes after sunrise or before sunset
+    Minutes(Minutes),
+}
+
+/// length of the zawal around the transit of the sun
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZawalWindow {
+    /// while the disk of the sun crosses the meridian, about a minute before and after the transit
+    SemiDiameter,
+    /// minutes before and after the transit
+    Minutes(Minutes),
+}
+
+/// lengths of the forbidden intervals
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ForbiddenRules {
+    /// until the sun has risen a spear's length ( default is 15 minutes )
+    pub after_sunrise: ForbiddenLimit,
+    /// around the transit ( default is the semi-diameter of the sun )
+    pub zawal: ZawalWindow,
+    /// when the sun is low before sunset ( default is 15 minutes )
+    pub before_sunset: ForbiddenLimit,
+}
+
+impl Default for ForbiddenRules {
+    fn default() -> Self {
+        Self {
+            after_sunrise: ForbiddenLimit::Minutes(Minutes { minutes: 15.0 }),
+            zawal: ZawalWindow::SemiDiameter,
+            before_sunset: ForbiddenLimit::Minutes(Minutes { minutes: 15.0 }),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum ForbiddenKind {
+    AfterSunrise,
+    Zawal,
+    BeforeSunset,
+}
+
+/// a forbidden interval of a day, in utc
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ForbiddenInterval {
+    pub kind: ForbiddenKind,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+pub(crate) fn intervals(
+    calculator: &InternalCalculator,
+    rules: &ForbiddenRules,
+) -> Vec<ForbiddenInterval> {
+    let sunrise = calculator.sunrise();
+    let sunset = calculator.sunset();
+    let transit = calculator.mid_day(calculator.estimates.dhuhr);
+
+    let after_sunrise = match rules.after_sunrise {
+        ForbiddenLimit::Minutes(Minutes { minutes }) => sunrise + minutes / 60.0,
+        ForbiddenLimit::Altitude(Degrees { degree }) => {
+            calculator.altitude_time(degree, calculator.estimates.sunrise, NoonSide::BeforeNoon)
+        }
+    };
+    let before_sunset = match rules.before_sunset {
+        ForbiddenLimit::Minutes(Minutes { minutes }) => sunset - minutes / 60.0,
+        ForbiddenLimit::Altitude(Degrees { degree }) => {
+            calculator.altitude_time(degree, calculator.estimates.sunset, NoonSide::AfterNoon)
+        }
+    };
+    let zawal = match rules.zawal {
+        ZawalWindow::SemiDiameter => calculator.semi_diameter_transit(calculator.estimates.dhuhr),
+        ZawalWindow::Minutes(Minutes { minutes }) => minutes / 60.0,
+    };
+
+    [
+        (ForbiddenKind::AfterSunrise, sunrise, after_sunrise),
+        (ForbiddenKind::Zawal, transit - zawal, transit + zawal),
+        (ForbiddenKind::BeforeSunset, before_sunset, sunset),
+    ]
+    .into_iter()
+    .filter_map(|(kind, start, end)| {
+        Some(ForbiddenInterval {
+            kind,
+            start: calculator.datetime_from_hours(start)?,
+            end: calculator.datetime_from_hours(end)?,
+        })
+    })
+    .collect()
+}
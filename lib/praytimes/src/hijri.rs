@@ -0,0 +1,357 @@
+//! Hijri (islamic lunar) calendar conversions
+//!
+//! two families of calendars are supported
+//! - [`Calendar::UmmAlQura`] the official calendar of Saudi Arabia, computed from its published criterion
+//! - [`Calendar::Tabular`] the arithmetic calendar with a 30 year intercalation cycle
+//!
+//! ```rust
+//! use chrono::NaiveDate;
+//! use praytimes::hijri::{HijriDate, UMM_AL_QURA};
+//!
+//! let date = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+//! assert_eq!(UMM_AL_QURA.to_hijri(&date), Some(HijriDate { year: 1445, month: 9, day: 1 }));
+//! ```
+use chrono::{Datelike, Duration, NaiveDate};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+use crate::utils::float::Float;
+use crate::{
+    internal_calculator::{Estimates, InternalCalculator},
+    methods,
+    solar::Almanac,
+    types::{Location, PraytimeType},
+    utils::{
+        julian_date::date_from_julian,
+        moon::{lunation_near, moon_altitude, moon_horizon_altitude, new_moon},
+    },
+};
+
+/// date in the hijri calendar
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct HijriDate {
+    pub year: i32,
+    /// month of the year, 1 (Muharram) to 12 (Dhu al-Hijjah)
+    pub month: u32,
+    /// day of the month, 1 to 30
+    pub day: u32,
+}
+
+/// leap year patterns of the 30 year cycle used by the tabular calendar
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum LeapYears {
+    /// 2, 5, 7, 10, 13, 15, 18, 21, 24, 26, 29 (Kushyar ibn Labban)
+    Type1,
+    /// 2, 5, 7, 10, 13, 16, 18, 21, 24, 26, 29 (the most common one, also used by the Kuwaiti algorithm)
+    Type2,
+    /// 2, 5, 8, 10, 13, 16, 19, 21, 24, 27, 29 (al-Fazari)
+    Type3,
+    /// 2, 5, 8, 11, 13, 16, 19, 21, 24, 27, 30 (Habash al-Hasib, used by the Bohras)
+    Type4,
+}
+
+impl LeapYears {
+    fn years(&self) -> [i64; 11] {
+        match self {
+            LeapYears::Type1 => [2, 5, 7, 10, 13, 15, 18, 21, 24, 26, 29],
+            LeapYears::Type2 => [2, 5, 7, 10, 13, 16, 18, 21, 24, 26, 29],
+            LeapYears::Type3 => [2, 5, 8, 10, 13, 16, 19, 21, 24, 27, 29],
+            LeapYears::Type4 => [2, 5, 8, 11, 13, 16, 19, 21, 24, 27, 30],
+        }
+    }
+}
+
+/// epoch of the tabular calendar
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Epoch {
+    /// 1 Muharram 1 AH is Friday 16 July 622 (julian calendar)
+    Civil,
+    /// 1 Muharram 1 AH is Thursday 15 July 622 (julian calendar)
+    Astronomical,
+}
+
+impl Epoch {
+    /// day number (days from 0001-01-01 of the proleptic gregorian calendar, starting at 1) of 1 Muharram 1 AH
+    fn day_number(&self) -> i64 {
+        match self {
+            Epoch::Civil => 227015,
+            Epoch::Astronomical => 227014,
+        }
+    }
+}
+
+/// the way months are determined
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Calendar {
+    /// Umm al-Qura calendar of Saudi Arabia
+    ///
+    /// a month starts on the day after the 29th, when on the evening of the 29th the conjunction happens
+    /// before sunset and the moon sets after the sun in Makkah, otherwise on the day after.
+    /// this is the criterion the official tables are built from since 1420 AH
+    UmmAlQura,
+    /// arithmetic calendar, months alternate between 30 and 29 days and leap years add a day to the last month
+    Tabular { leap_years: LeapYears, epoch: Epoch },
+}
+
+/// hijri calendar with an adjustment in days
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct HijriCalendar {
+    pub calendar: Calendar,
+    /// days added to the calculated hijri date, to follow local moon sighting
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub adjustment: i32,
+}
+
+pub const UMM_AL_QURA: HijriCalendar = HijriCalendar {
+    calendar: Calendar::UmmAlQura,
+    adjustment: 0,
+};
+
+pub const TABULAR: HijriCalendar = HijriCalendar {
+    calendar: Calendar::Tabular {
+        leap_years: LeapYears::Type2,
+        epoch: Epoch::Civil,
+    },
+    adjustment: 0,
+};
+
+/// months from 1 Muharram 1 AH to the month that began with lunation 0 (Shawwal 1420)
+const LUNATION_0_MONTH: i64 = 1419 * 12 + 9;
+
+const MAKKAH: Location = Location {
+    latitude: 21.4225,
+    longitude: 39.8262,
+    elevation: 0.0,
+    atmosphere: None,
+};
+
+impl HijriCalendar {
+    /// new calendar with an adjustment of `days` days
+    pub fn with_adjustment(self, days: i32) -> Self {
+        Self {
+            adjustment: days,
+            ..self
+        }
+    }
+
+    /// convert a gregorian date to hijri
+    pub fn to_hijri(&self, date: &NaiveDate) -> Option<HijriDate> {
+        let date = date.checked_add_signed(Duration::days(self.adjustment.into()))?;
+        match self.calendar {
+            Calendar::UmmAlQura => umm_al_qura_to_hijri(&date),
+            Calendar::Tabular { leap_years, epoch } => tabular_to_hijri(&date, leap_years, epoch),
+        }
+    }
+
+    /// convert a hijri date to gregorian, None if the date doesn't exist in this calendar
+    pub fn from_hijri(&self, date: &HijriDate) -> Option<NaiveDate> {
+        if !(1..=12).contains(&date.month) || !(1..=30).contains(&date.day) {
+            return None;
+        }
+        let gregorian = match self.calendar {
+            Calendar::UmmAlQura => umm_al_qura_from_hijri(date),
+            Calendar::Tabular { leap_years, epoch } => tabular_from_hijri(date, leap_years, epoch),
+        }?;
+        gregorian.checked_sub_signed(Duration::days(self.adjustment.into()))
+    }
+
+    /// number of days in a hijri month ( 29 or 30 )
+    pub fn month_length(&self, year: i32, month: u32) -> Option<u32> {
+        if let Calendar::UmmAlQura = self.calendar {
+            if !(1..=12).contains(&month) {
+                return None;
+            }
+            let (start, next) = umm_al_qura_month(umm_al_qura_lunation(year, month))?;
+            return Some((next - start).num_days() as u32);
+        }
+        let first = HijriDate {
+            year,
+            month,
+            day: 1,
+        };
+        let start = self.from_hijri(&first)?;
+        let end = match month {
+            12 => self.from_hijri(&HijriDate {
+                year: year + 1,
+                month: 1,
+                day: 1,
+            }),
+            _ => self.from_hijri(&HijriDate {
+                month: month + 1,
+                ..first
+            }),
+        }?;
+        Some((end - start).num_days() as u32)
+    }
+}
+
+impl Default for HijriCalendar {
+    fn default() -> Self {
+        UMM_AL_QURA
+    }
+}
+
+fn year_month(months: i64) -> Option<(i32, u32)> {
+    let year = months.div_euclid(12) + 1;
+    Some((year.try_into().ok()?, (months.rem_euclid(12) + 1) as u32))
+}
+
+fn months_since_epoch(year: i32, month: u32) -> i64 {
+    (year as i64 - 1) * 12 + month as i64 - 1
+}
+
+/// whether the month of lunation `k` begins the day after `day`, the conjunction happens before the sunset of
+/// `day` and the moon sets after the sun in makkah
+fn umm_al_qura_sighted(k: f64, day: &NaiveDate) -> Option<bool> {
+    let conjunction = new_moon(k);
+
+    // the sunset doesn't depend on the method, only the sunset is computed without the hijri rules that would
+    // need this calendar
+    let estimates = Estimates::default().only(&[PraytimeType::Sunset]);
+    let sunset = InternalCalculator::new(
+        &methods::MUSLIM_WORLD_LEAGUE,
+        &Almanac,
+        &MAKKAH,
+        day,
+        estimates,
+    )
+    .sunset();
+    if sunset.is_nan() {
+        return None;
+    }
+    let sunset = day.num_days_from_ce() as f64 + 1721424.5 + sunset / 24.0;
+
+    let moon_sets_after_sun =
+        moon_altitude(sunset, MAKKAH.latitude, MAKKAH.longitude) > moon_horizon_altitude(sunset);
+
+    Some(conjunction < sunset && moon_sets_after_sun)
+}
+
+/// first day of the month of lunation `k` from the day of its conjunction in makkah ( UTC+3 ), the 29th of the
+/// month before in most months
+fn umm_al_qura_conjunction_start(k: f64) -> Option<NaiveDate> {
+    let day = date_from_julian(new_moon(k) + 3.0 / 24.0)?;
+    if umm_al_qura_sighted(k, &day)? {
+        day.succ_opt()
+    } else {
+        day.succ_opt()?.succ_opt()
+    }
+}
+
+/// first day of the month of lunation `k` after a month starting on `previous`, the day after its 29th or its 30th
+fn umm_al_qura_next_start(k: f64, previous: &NaiveDate) -> Option<NaiveDate> {
+    let day_29 = previous.checked_add_signed(Duration::days(28))?;
+    let length = if umm_al_qura_sighted(k, &day_29)? {
+        29
+    } else {
+        30
+    };
+    previous.checked_add_signed(Duration::days(length))
+}
+
+/// first day and first day of the next month of the umm al-qura month of lunation `k`
+///
+/// the month before is found from its conjunction, then the sighting is checked on the 29th of each month so the
+/// months are always 29 or 30 days long
+fn umm_al_qura_month(k: f64) -> Option<(NaiveDate, NaiveDate)> {
+    let previous = umm_al_qura_conjunction_start(k - 1.0)?;
+    let start = umm_al_qura_next_start(k, &previous)?;
+    Some((start, umm_al_qura_next_start(k + 1.0, &start)?))
+}
+
+fn umm_al_qura_to_hijri(date: &NaiveDate) -> Option<HijriDate> {
+    let jd = date.num_days_from_ce() as f64 + 1721424.5;
+    let mut k = lunation_near(jd);
+    let (mut start, mut next) = umm_al_qura_month(k)?;
+    while start > *date {
+        k -= 1.0;
+        (start, next) = umm_al_qura_month(k)?;
+    }
+    while next <= *date {
+        k += 1.0;
+        (start, next) = (next, umm_al_qura_next_start(k + 1.0, &next)?);
+    }
+    let (year, month) = year_month(k as i64 + LUNATION_0_MONTH)?;
+
+    Some(HijriDate {
+        year,
+        month,
+        day: (*date - start).num_days() as u32 + 1,
+    })
+}
+
+fn umm_al_qura_from_hijri(date: &HijriDate) -> Option<NaiveDate> {
+    let (start, next) = umm_al_qura_month(umm_al_qura_lunation(date.year, date.month))?;
+    let gregorian = start.checked_add_signed(Duration::days(date.day as i64 - 1))?;
+
+    (gregorian < next).then_some(gregorian)
+}
+
+/// lunation of the new moon before an umm al-qura month
+fn umm_al_qura_lunation(year: i32, month: u32) -> f64 {
+    (months_since_epoch(year, month) - LUNATION_0_MONTH) as f64
+}
+
+/// number of leap years among the first `years` years
+fn leap_years_before(years: i64, leap_years: LeapYears) -> i64 {
+    let in_cycle = years.rem_euclid(30);
+    years.div_euclid(30) * 11
+        + leap_years
+            .years()
+            .iter()
+            .filter(|y| **y <= in_cycle)
+            .count() as i64
+}
+
+fn tabular_year_length(year: i64, leap_years: LeapYears) -> i64 {
+    354 + leap_years_before(year, leap_years) - leap_years_before(year - 1, leap_years)
+}
+
+/// days from the epoch to the first day of a month
+fn tabular_days_before(year: i64, month: i64, leap_years: LeapYears) -> i64 {
+    (year - 1) * 354
+        + leap_years_before(year - 1, leap_years)
+        + (29.5 * (month - 1) as f64).ceil() as i64
+}
+
+fn tabular_to_hijri(date: &NaiveDate, leap_years: LeapYears, epoch: Epoch) -> Option<HijriDate> {
+    let days = date.num_days_from_ce() as i64 - epoch.day_number();
+
+    // 10631 days in each 30 year cycle
+    let mut year = days.div_euclid(10631) * 30 + 1;
+    while tabular_days_before(year + 1, 1, leap_years) <= days {
+        year += 1;
+    }
+    let mut month = 1;
+    while month < 12 && tabular_days_before(year, month + 1, leap_years) <= days {
+        month += 1;
+    }
+
+    Some(HijriDate {
+        year: year.try_into().ok()?,
+        month: month as u32,
+        day: (days - tabular_days_before(year, month, leap_years) + 1) as u32,
+    })
+}
+
+fn tabular_from_hijri(date: &HijriDate, leap_years: LeapYears, epoch: Epoch) -> Option<NaiveDate> {
+    let year = date.year as i64;
+    let month = date.month as i64;
+    let length = match month {
+        12 => tabular_year_length(year, leap_years) - 325,
+        _ => 30 - (month - 1) % 2,
+    };
+    if date.day as i64 > length {
+        return None;
+    }
+
+    let days = tabular_days_before(year, month, leap_years) + date.day as i64 - 1;
+    NaiveDate::from_num_days_from_ce_opt((days + epoch.day_number()).try_into().ok()?)
+}
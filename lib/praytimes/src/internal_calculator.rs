@@ -1,181 +1,744 @@
-use chrono::NaiveDateTime;
+use alloc::collections::BTreeMap;
+use chrono::{Datelike, Duration, NaiveDateTime};
+use core::cell::RefCell;
 
 use crate::types::{
-    CalculationUnit, Degrees, HighLatsMethod, Location, MidnightMethod, Minutes, PraytimesOutput,
+    CalculationUnit, Degrees, HighLatsMethod, Location, MidnightMethod, Minutes, NoonSide,
+    PraytimeType, PraytimesHours, PraytimesOutput, Seasonal, Shafaq, SUN_SEMI_DIAMETER,
 };
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+use crate::utils::float::Float;
 
-use crate::utils::{d_math, numbers::fix_hour, sun_position::sun_position};
+use crate::solar::{DailyPositions, SolarModel, SunPosition};
+use crate::utils::{d_math, julian_date::to_julian_date, numbers::fix_hour};
 
 use chrono::NaiveDate;
 
 use crate::types::Parameters;
 
+/// latitude of the [`HighLatsMethod::NearestLatitude`] method
+const NEAREST_LATITUDE: f64 = 48.5;
+
+/// estimated times of the events as a portion of the local mean day, where the sun position is evaluated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimates {
+    pub imsak: f64,
+    pub fajr: f64,
+    pub sunrise: f64,
+    pub dhuhr: f64,
+    pub asr: f64,
+    pub sunset: f64,
+    pub maghrib: f64,
+    pub isha: f64,
+}
+
+impl Default for Estimates {
+    /// the initial guesses of PrayTimes.js
+    fn default() -> Self {
+        Self {
+            imsak: 5.0 / 24.0,
+            fajr: 5.0 / 24.0,
+            sunrise: 6.0 / 24.0,
+            dhuhr: 12.0 / 24.0,
+            asr: 13.0 / 24.0,
+            sunset: 18.0 / 24.0,
+            maghrib: 18.0 / 24.0,
+            isha: 18.0 / 24.0,
+        }
+    }
+}
+
+impl Estimates {
+    /// largest difference with other estimates in seconds
+    pub fn difference(&self, other: &Estimates) -> f64 {
+        [
+            self.imsak - other.imsak,
+            self.fajr - other.fajr,
+            self.sunrise - other.sunrise,
+            self.dhuhr - other.dhuhr,
+            self.asr - other.asr,
+            self.sunset - other.sunset,
+            self.maghrib - other.maghrib,
+            self.isha - other.isha,
+        ]
+        .into_iter()
+        .fold(0.0_f64, |max, difference| max.max(difference.abs()))
+            * 86400.0
+    }
+
+    /// the estimates of some events, the other events are left out ( NaN ) so their solar quantities aren't
+    /// computed
+    pub(crate) fn only(&self, events: &[PraytimeType]) -> Estimates {
+        let keep = |kept: PraytimeType, estimate: f64| {
+            if events.contains(&kept) {
+                estimate
+            } else {
+                f64::NAN
+            }
+        };
+        Estimates {
+            imsak: keep(PraytimeType::Imsak, self.imsak),
+            fajr: keep(PraytimeType::Fajr, self.fajr),
+            sunrise: keep(PraytimeType::Sunrise, self.sunrise),
+            dhuhr: keep(PraytimeType::Dhuhr, self.dhuhr),
+            asr: keep(PraytimeType::Asr, self.asr),
+            sunset: keep(PraytimeType::Sunset, self.sunset),
+            maghrib: keep(PraytimeType::Maghrib, self.maghrib),
+            isha: keep(PraytimeType::Isha, self.isha),
+        }
+    }
+
+    fn times(&self) -> [f64; 8] {
+        [
+            self.imsak,
+            self.fajr,
+            self.sunrise,
+            self.dhuhr,
+            self.asr,
+            self.sunset,
+            self.maghrib,
+            self.isha,
+        ]
+    }
+}
+
+/// solar quantities of a day computed once for the estimates, the sun position at each estimate and the sunrise
+/// and sunset that most events depend on
+#[derive(Debug, Clone, Copy)]
+struct SolarDay {
+    times: [f64; 8],
+    positions: [SunPosition; 8],
+    sunrise: f64,
+    sunset: f64,
+}
+
+impl SolarDay {
+    /// nothing computed yet, the times never match since NaN isn't equal to itself
+    const EMPTY: SolarDay = SolarDay {
+        times: [f64::NAN; 8],
+        positions: [SunPosition {
+            declination: f64::NAN,
+            equation: f64::NAN,
+        }; 8],
+        sunrise: f64::NAN,
+        sunset: f64::NAN,
+    };
+}
+
+/// solar quantities shared by the calculations of a location, so a day is calculated the same whatever the days
+/// calculated before
+#[derive(Debug)]
+pub(crate) struct DayCache<'a> {
+    /// the sun positions of the days ( see [`DailyPositions`] )
+    positions: DailyPositions<'a>,
+    /// portions of the night between the degree based events and their bases on the days searched by the
+    /// [`HighLatsMethod::NearestDay`] method ( NaN when the event doesn't exist on a day ), calculated from the
+    /// default estimates so any calculation of the location can reuse them
+    portions: RefCell<BTreeMap<(PraytimeType, u64, NaiveDate), f64>>,
+}
+
+impl<'a> DayCache<'a> {
+    pub(crate) fn new(solar_model: &'a dyn SolarModel) -> Self {
+        DayCache {
+            positions: DailyPositions::new(solar_model),
+            portions: RefCell::new(BTreeMap::new()),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
 pub struct InternalCalculator<'a> {
     pub params: &'a Parameters,
+    pub solar_model: &'a dyn SolarModel,
     pub location: &'a Location,
     pub date: &'a NaiveDate,
     pub julian_date: f64,
+    pub estimates: Estimates,
+    solar: SolarDay,
+    cache: Option<&'a DayCache<'a>>,
 }
 
 impl<'a> InternalCalculator<'a> {
+    pub fn new(
+        params: &'a Parameters,
+        solar_model: &'a dyn SolarModel,
+        location: &'a Location,
+        date: &'a NaiveDate,
+        estimates: Estimates,
+    ) -> Self {
+        Self {
+            params,
+            solar_model,
+            location,
+            date,
+            julian_date: to_julian_date(date, location),
+            estimates,
+            solar: SolarDay::EMPTY,
+            cache: None,
+        }
+        .with_estimates(estimates)
+    }
+
+    /// like [`InternalCalculator::new`] with the solar model of a cache, the sun positions are interpolated and the
+    /// nearest days of the [`HighLatsMethod::NearestDay`] method are searched once for all the calculations sharing
+    /// it, they must be of the same location
+    pub(crate) fn cached(
+        params: &'a Parameters,
+        location: &'a Location,
+        date: &'a NaiveDate,
+        estimates: Estimates,
+        cache: &'a DayCache<'a>,
+    ) -> Self {
+        Self {
+            params,
+            solar_model: cache.positions.solar_model(),
+            location,
+            date,
+            julian_date: to_julian_date(date, location),
+            estimates,
+            solar: SolarDay::EMPTY,
+            cache: Some(cache),
+        }
+        .with_estimates(estimates)
+    }
+
+    /// the calculator of other estimates, with the solar quantities computed for them
+    pub fn with_estimates(self, estimates: Estimates) -> Self {
+        let calculator = Self {
+            estimates,
+            solar: SolarDay::EMPTY,
+            ..self
+        };
+        Self {
+            solar: calculator.solar_day(),
+            ..calculator
+        }
+    }
+
+    fn solar_day(&self) -> SolarDay {
+        let times = self.estimates.times();
+        let mut positions = SolarDay::EMPTY.positions;
+        for i in 0..times.len() {
+            // the initial estimates share some times
+            positions[i] = match times[..i].iter().position(|time| *time == times[i]) {
+                Some(same) => positions[same],
+                None => self.sun_position(times[i]),
+            };
+        }
+        let calculator = Self {
+            solar: SolarDay {
+                times,
+                positions,
+                ..SolarDay::EMPTY
+            },
+            ..*self
+        };
+
+        let angle = self.rise_set_angle();
+        let sunrise = self.estimates.sunrise;
+        let sunset = self.estimates.sunset;
+        SolarDay {
+            sunrise: calculator.mid_day(sunrise) - calculator.sat(sunrise, angle),
+            sunset: calculator.mid_day(sunset) + calculator.sat(sunset, angle),
+            ..calculator.solar
+        }
+    }
+
+    /// position of the sun at a time of the day, computed once for the estimates
+    fn sun_position(&self, time: f64) -> SunPosition {
+        // an estimate left out ( see `Estimates::only` )
+        if time.is_nan() {
+            return SolarDay::EMPTY.positions[0];
+        }
+        match self
+            .solar
+            .times
+            .iter()
+            .position(|estimate| *estimate == time)
+        {
+            Some(i) => self.solar.positions[i],
+            None => match self.cache {
+                Some(cache) => cache.positions.sun_position(self.julian_date + time),
+                None => self.solar_model.sun_position(self.julian_date + time),
+            },
+        }
+    }
+
     pub fn calculate(&self) -> PraytimesOutput {
+        let hours = self.hours();
+        let datetime = |hours: Option<f64>| hours.and_then(|hours| self.datetime_from_hours(hours));
+
         PraytimesOutput {
-            imsak: self.datetime_from_hours(self.imsak()),
-            fajr: self.datetime_from_hours(self.fajr()),
-            sunrise: self.datetime_from_hours(self.sunrise()),
-            dhuhr: self.datetime_from_hours(self.dhuhr()),
-            asr: self.datetime_from_hours(self.asr()),
-            sunset: self.datetime_from_hours(self.sunset()),
-            maghrib: self.datetime_from_hours(self.maghrib()),
-            isha: self.datetime_from_hours(self.isha()),
-            midnight: self.datetime_from_hours(self.midnight()),
+            imsak: datetime(hours.imsak),
+            fajr: datetime(hours.fajr),
+            sunrise: datetime(hours.sunrise),
+            dhuhr: datetime(hours.dhuhr),
+            asr: datetime(hours.asr),
+            sunset: datetime(hours.sunset),
+            maghrib: datetime(hours.maghrib),
+            isha: datetime(hours.isha),
+            midnight: datetime(hours.midnight),
+            hijri: None,
+            adjusted: hours.adjusted,
+        }
+    }
+
+    /// the times in fractional utc hours of the day, `None` for the events that don't exist
+    pub fn hours(&self) -> PraytimesHours {
+        let (imsak, imsak_adjusted) = self.imsak_time();
+        let (fajr, fajr_adjusted) = self.fajr_time();
+        let (maghrib, maghrib_adjusted) = self.maghrib_time();
+        let (isha, isha_adjusted) = self.isha_time();
+
+        let adjusted = [
+            (PraytimeType::Imsak, imsak, imsak_adjusted),
+            (PraytimeType::Fajr, fajr, fajr_adjusted),
+            (PraytimeType::Maghrib, maghrib, maghrib_adjusted),
+            (PraytimeType::Isha, isha, isha_adjusted),
+        ]
+        .into_iter()
+        .filter(|(_, time, adjusted)| *adjusted && time.is_finite())
+        .map(|(event, _, _)| event)
+        .collect();
+
+        let hours = |hours: f64| (!hours.is_nan()).then_some(hours);
+        PraytimesHours {
+            imsak: hours(imsak),
+            fajr: hours(fajr),
+            sunrise: hours(self.sunrise()),
+            dhuhr: hours(self.dhuhr()),
+            asr: hours(self.asr()),
+            sunset: hours(self.sunset()),
+            maghrib: hours(maghrib),
+            isha: hours(isha),
+            midnight: hours(self.midnight()),
+            adjusted,
+        }
+    }
+
+    /// estimates for the next iteration, the calculated times ( or the previous estimate when an event doesn't exist )
+    pub fn refined_estimates(&self) -> Estimates {
+        let refine = |hours: f64, estimate: f64| {
+            let portion = (hours + self.location.longitude / 15.0) / 24.0;
+            if portion.is_finite() {
+                portion
+            } else {
+                estimate
+            }
+        };
+        let estimates = &self.estimates;
+
+        Estimates {
+            imsak: refine(self.imsak(), estimates.imsak),
+            fajr: refine(self.fajr(), estimates.fajr),
+            sunrise: refine(self.sunrise(), estimates.sunrise),
+            dhuhr: refine(self.dhuhr(), estimates.dhuhr),
+            asr: refine(self.asr(), estimates.asr),
+            sunset: refine(self.sunset(), estimates.sunset),
+            maghrib: refine(self.maghrib(), estimates.maghrib),
+            isha: refine(self.isha(), estimates.isha),
         }
     }
 
     pub(crate) fn midnight(&self) -> f64 {
+        self.sunset() + self.night_length() / 2.0
+    }
+
+    /// hours from sunset to sunrise, or to fajr with the jafari midnight method
+    pub(crate) fn night_length(&self) -> f64 {
         let sunset = self.sunset();
-        let sunrise = self.sunrise();
-        let midnight = match self.params.midnight {
-            MidnightMethod::Standard => sunset + Self::time_difference(sunset, sunrise) / 2.0,
-            MidnightMethod::Jafari => sunset + Self::time_difference(sunset, self.fajr()) / 2.0,
-        };
 
-        midnight
+        match self.params.midnight {
+            MidnightMethod::Standard => Self::time_difference(sunset, self.sunrise()),
+            MidnightMethod::Jafari => Self::time_difference(sunset, self.fajr()),
+        }
     }
 
     pub(crate) fn asr(&self) -> f64 {
-        self.asr_time(self.params.asr.factor, 13.0 / 24.0)
+        self.shadow_time(
+            self.params.asr.factor,
+            self.estimates.asr,
+            NoonSide::AfterNoon,
+        )
     }
 
-    pub(crate) fn asr_time(&self, factor: f64, time: f64) -> f64 {
-        let decl = sun_position(self.julian_date + time).declination;
+    /// time when the shadow of an object is its noon shadow plus `factor` times its length
+    pub(crate) fn shadow_time(&self, factor: f64, time: f64, side: NoonSide) -> f64 {
+        let decl = self.sun_position(time).declination;
         let angle = -d_math::arccot(factor + d_math::tan((self.location.latitude - decl).abs()));
-        self.mid_day(time) + self.sat(time, angle)
+        match side {
+            NoonSide::BeforeNoon => self.mid_day(time) - self.sat(time, angle),
+            NoonSide::AfterNoon => self.mid_day(time) + self.sat(time, angle),
+        }
+    }
+
+    /// time when the center of the sun is seen at an apparent altitude above the horizon ( like
+    /// [`crate::solar::SunCoordinates::altitude`], in the standard atmosphere by default )
+    pub(crate) fn altitude_time(&self, altitude: f64, time: f64, side: NoonSide) -> f64 {
+        let refraction = self
+            .location
+            .atmosphere
+            .unwrap_or_default()
+            .refraction(altitude);
+        let angle = refraction - altitude;
+        match side {
+            NoonSide::BeforeNoon => self.mid_day(time) - self.sat(time, angle),
+            NoonSide::AfterNoon => self.mid_day(time) + self.sat(time, angle),
+        }
+    }
+
+    /// hours the edge of the sun takes to reach the meridian after ( or before ) its center
+    pub(crate) fn semi_diameter_transit(&self, time: f64) -> f64 {
+        let decl = self.sun_position(time).declination;
+        SUN_SEMI_DIAMETER / (15.0 * d_math::cos(decl))
     }
 
     pub fn sat(&self, time: f64, angle: f64) -> f64 {
-        let decl = sun_position(self.julian_date + time).declination;
+        self.sat_at(time, angle, self.location.latitude)
+    }
+
+    /// like [`InternalCalculator::sat`] at another latitude, the position of the sun is the same
+    fn sat_at(&self, time: f64, angle: f64, latitude: f64) -> f64 {
+        let decl = self.sun_position(time).declination;
 
         (1.0 / 15.0)
             * d_math::arccos(
-                (-d_math::sin(angle) - d_math::sin(decl) * d_math::sin(self.location.latitude))
-                    / (d_math::cos(decl) * d_math::cos(self.location.latitude)),
+                (-d_math::sin(angle) - d_math::sin(decl) * d_math::sin(latitude))
+                    / (d_math::cos(decl) * d_math::cos(latitude)),
             )
     }
 
     pub fn sunrise(&self) -> f64 {
-        let angle = self.rise_set_angle();
-        let time = self.mid_day(6.0 / 24.0) - self.sat(6.0 / 24.0, angle);
-        time
+        self.solar.sunrise
     }
 
     pub fn rise_set_angle(&self) -> f64 {
         let angle = 0.0347 * self.location.elevation.sqrt();
 
-        let angle = 0.833 + angle;
+        match self.location.atmosphere {
+            Some(atmosphere) => SUN_SEMI_DIAMETER + atmosphere.refraction(0.0) + angle,
+            None => 0.833 + angle,
+        }
+    }
 
-        angle
+    /// sun angle below the horizon of a degree based event, corrected for the refraction
+    ///
+    /// only the angles closer to the horizon than sunrise and sunset are refracted ( see
+    /// [`crate::types::Atmosphere::refraction`] ), the usual twilight angles are the same in any atmosphere
+    pub(crate) fn twilight_angle(&self, angle: f64) -> f64 {
+        match self.location.atmosphere {
+            Some(atmosphere) => angle + atmosphere.refraction(-angle),
+            None => angle,
+        }
     }
 
     pub fn sunset(&self) -> f64 {
-        let angle = self.rise_set_angle();
-        let time = self.mid_day(18.0 / 24.0) + self.sat(18.0 / 24.0, angle);
-        time
+        self.solar.sunset
     }
 
     pub(crate) fn mid_day(&self, time: f64) -> f64 {
-        let sun_pos = sun_position(self.julian_date + time);
+        let sun_pos = self.sun_position(time);
 
         let eqt = sun_pos.equation;
 
-        let noon = fix_hour(12.0 - eqt) - self.location.longitude / 15.0;
-
-        noon
+        fix_hour(12.0 - eqt) - self.location.longitude / 15.0
     }
 
     pub(crate) fn dhuhr(&self) -> f64 {
-        let mid_day = self.mid_day(12.0 / 24.0);
-        let var_name = mid_day + self.params.dhuhr.minutes / 60.0;
-        var_name
+        let mid_day = self.mid_day(self.estimates.dhuhr);
+        mid_day + self.params.dhuhr.minutes / 60.0
     }
 
     pub fn imsak(&self) -> f64 {
+        self.imsak_time().0
+    }
+
+    /// imsak and whether it was adjusted for the high latitudes
+    pub(crate) fn imsak_time(&self) -> (f64, bool) {
         match self.params.imsak {
             CalculationUnit::Degrees(Degrees { degree: angle }) => {
-                let time = self.mid_day(5.0 / 24.0) - self.sat(5.0 / 24.0, angle);
-                let base = self.sunrise();
-                if self.high_lat_adjustment_needed(time, base, angle) {
-                    base - self.night_portion(angle)
-                } else {
-                    time
-                }
+                self.adjusted_twilight(PraytimeType::Imsak, angle)
+            }
+            CalculationUnit::Seasonal(seasonal) => {
+                self.seasonal_twilight(PraytimeType::Imsak, seasonal)
+            }
+            CalculationUnit::Minutes(Minutes { minutes }) => {
+                let (fajr, adjusted) = self.fajr_time();
+                (fajr - minutes / 60.0, adjusted)
             }
-            CalculationUnit::Minutes(Minutes { minutes }) => self.fajr() - minutes / 60.0,
         }
     }
 
     pub fn maghrib(&self) -> f64 {
-        let base = self.sunset();
+        self.maghrib_time().0
+    }
 
+    /// maghrib and whether it was adjusted for the high latitudes
+    pub(crate) fn maghrib_time(&self) -> (f64, bool) {
         match self.params.maghrib {
-            CalculationUnit::Minutes(Minutes { minutes }) => base + minutes / 60.0,
+            CalculationUnit::Minutes(Minutes { minutes }) => {
+                (self.sunset() + minutes / 60.0, false)
+            }
             CalculationUnit::Degrees(Degrees { degree: angle }) => {
-                let time = self.mid_day(18.0 / 24.0) + self.sat(18.0 / 24.0, angle);
-                {
-                    let ref this = self;
-                    if this.high_lat_adjustment_needed(time, base, angle) {
-                        base + this.night_portion(angle)
-                    } else {
-                        time
-                    }
-                }
+                self.adjusted_twilight(PraytimeType::Maghrib, angle)
+            }
+            CalculationUnit::Seasonal(seasonal) => {
+                self.seasonal_twilight(PraytimeType::Maghrib, seasonal)
             }
         }
     }
 
     pub fn isha(&self) -> f64 {
+        self.isha_time().0
+    }
+
+    /// isha and whether it was adjusted for the high latitudes
+    pub(crate) fn isha_time(&self) -> (f64, bool) {
         match self.params.isha {
-            CalculationUnit::Minutes(Minutes { minutes }) => self.maghrib() + minutes / 60.0,
+            CalculationUnit::Minutes(Minutes { minutes }) => {
+                let (maghrib, adjusted) = self.maghrib_time();
+                (maghrib + minutes / 60.0, adjusted)
+            }
             CalculationUnit::Degrees(Degrees { degree: angle }) => {
-                let time = self.mid_day(18.0 / 24.0) + self.sat(18.0 / 24.0, angle);
-                if self.high_lat_adjustment_needed(time, self.sunset(), angle) {
-                    self.sunset() + self.night_portion(angle)
-                } else {
-                    time
-                }
+                self.adjusted_twilight(PraytimeType::Isha, angle)
+            }
+            CalculationUnit::Seasonal(seasonal) => {
+                self.seasonal_twilight(PraytimeType::Isha, seasonal)
             }
         }
     }
 
     pub fn fajr(&self) -> f64 {
-        let angle = self.params.fajr.degree;
-        let time = self.mid_day(5.0 / 24.0) - self.sat(5.0 / 24.0, angle);
-        if self.high_lat_adjustment_needed(time, self.sunrise(), angle) {
-            self.sunrise() - self.night_portion(angle)
-        } else {
-            time
+        self.fajr_time().0
+    }
+
+    /// fajr and whether it was adjusted for the high latitudes
+    pub(crate) fn fajr_time(&self) -> (f64, bool) {
+        match self.params.fajr {
+            CalculationUnit::Degrees(Degrees { degree: angle }) => {
+                self.adjusted_twilight(PraytimeType::Fajr, angle)
+            }
+            CalculationUnit::Seasonal(seasonal) => {
+                self.seasonal_twilight(PraytimeType::Fajr, seasonal)
+            }
+            CalculationUnit::Minutes(Minutes { minutes }) => {
+                (self.sunrise() - minutes / 60.0, false)
+            }
+        }
+    }
+
+    /// time of a degree based event ( sun below the horizon ) and its base, sunrise for the morning events and sunset
+    /// for the evening ones
+    fn twilight(&self, event: PraytimeType, angle: f64) -> (f64, f64) {
+        let time = self.twilight_at(event, angle, self.location.latitude);
+        match event {
+            PraytimeType::Imsak | PraytimeType::Fajr => (time, self.sunrise()),
+            _ => (time, self.sunset()),
+        }
+    }
+
+    /// time of a degree based event at another latitude, with the sun positions of the day
+    fn twilight_at(&self, event: PraytimeType, angle: f64, latitude: f64) -> f64 {
+        let angle = self.twilight_angle(angle);
+        match event {
+            PraytimeType::Imsak | PraytimeType::Fajr => {
+                let estimate = match event {
+                    PraytimeType::Imsak => self.estimates.imsak,
+                    _ => self.estimates.fajr,
+                };
+                self.mid_day(estimate) - self.sat_at(estimate, angle, latitude)
+            }
+            _ => {
+                let estimate = match event {
+                    PraytimeType::Maghrib => self.estimates.maghrib,
+                    _ => self.estimates.isha,
+                };
+                self.mid_day(estimate) + self.sat_at(estimate, angle, latitude)
+            }
+        }
+    }
+
+    /// sunrise and sunset at another latitude, with the sun positions of the day
+    fn horizon_at(&self, latitude: f64) -> (f64, f64) {
+        let angle = self.rise_set_angle();
+        let (sunrise, sunset) = (self.estimates.sunrise, self.estimates.sunset);
+        (
+            self.mid_day(sunrise) - self.sat_at(sunrise, angle, latitude),
+            self.mid_day(sunset) + self.sat_at(sunset, angle, latitude),
+        )
+    }
+
+    /// time of a degree based event after the adjustment for high latitudes, and whether it was adjusted
+    fn adjusted_twilight(&self, event: PraytimeType, angle: f64) -> (f64, bool) {
+        let (time, base) = self.twilight(event, angle);
+        match self.high_lat_adjustment(event, time, base, angle) {
+            Some(portion) => Self::limited(event, time, base, portion),
+            None => (time, false),
         }
     }
 
-    pub(crate) fn high_lat_adjustment_needed(&self, time: f64, base: f64, angle: f64) -> bool {
-        let portion = self.night_portion(angle);
-        let time_diff = (time - base).abs();
+    /// time of a seasonal event, no further than the seasonal minutes from its base below 55° of latitude and
+    /// adjusted by the high latitudes method above
+    fn seasonal_twilight(&self, event: PraytimeType, seasonal: Seasonal) -> (f64, bool) {
+        if self.location.latitude.abs() >= 55.0 {
+            return self.adjusted_twilight(event, seasonal.degree);
+        }
 
-        time.is_nan() || (self.params.high_latitudes != HighLatsMethod::None && time_diff > portion)
+        let (time, base) = self.twilight(event, seasonal.degree);
+        let limit = self.seasonal_minutes(event, seasonal.shafaq) / 60.0;
+        Self::limited(event, time, base, limit)
     }
 
-    pub(crate) fn night_portion(&self, angle: f64) -> f64 {
+    /// an event no further than some hours from its base ( or that far when it doesn't exist ), and whether the
+    /// limit moved it
+    fn limited(event: PraytimeType, time: f64, base: f64, limit: f64) -> (f64, bool) {
+        if !time.is_nan() && (time - base).abs() <= limit {
+            return (time, false);
+        }
+
+        match event {
+            PraytimeType::Imsak | PraytimeType::Fajr => (base - limit, true),
+            _ => (base + limit, true),
+        }
+    }
+
+    /// the longest time between an event and its base in hours when the high latitudes method may move it, the
+    /// methods that only replace the missing events don't compute it for the existing ones
+    fn high_lat_adjustment(
+        &self,
+        event: PraytimeType,
+        time: f64,
+        base: f64,
+        angle: f64,
+    ) -> Option<f64> {
+        match self.params.high_latitudes {
+            HighLatsMethod::None | HighLatsMethod::NearestDay if !time.is_nan() => None,
+            _ => {
+                let portion = self.night_portion(event, angle);
+                (time.is_nan() || (time - base).abs() > portion).then_some(portion)
+            }
+        }
+    }
+
+    /// the longest time between the event and its base, in hours
+    pub(crate) fn night_portion(&self, event: PraytimeType, angle: f64) -> f64 {
         let night = self.night_time();
         let portion = match self.params.high_latitudes {
             HighLatsMethod::AngleBased => angle / 60.0,
             HighLatsMethod::OneSeventh => 1.0 / 7.0,
             HighLatsMethod::None => f64::NAN,
             HighLatsMethod::NightMiddle => 1.0 / 2.0, // default to MidNight
+            HighLatsMethod::MoonsightingCommittee if self.location.latitude.abs() >= 55.0 => {
+                1.0 / 7.0
+            }
+            HighLatsMethod::MoonsightingCommittee => {
+                let shafaq = match self.params.isha {
+                    CalculationUnit::Seasonal(Seasonal { shafaq, .. }) => shafaq,
+                    _ => Shafaq::General,
+                };
+                return self.seasonal_minutes(event, shafaq) / 60.0;
+            }
+            HighLatsMethod::NearestLatitude => self.nearest_latitude_portion(event, angle),
+            HighLatsMethod::NearestDay => self.nearest_day_portion(event, angle),
         };
 
         portion * night
     }
 
+    /// minutes between the event and its base in the seasonal method of the Moonsighting Committee ( Khalid Shaukat )
+    ///
+    /// the shafaq only changes the evening events
+    fn seasonal_minutes(&self, event: PraytimeType, shafaq: Shafaq) -> f64 {
+        let latitude = self.location.latitude.abs() / 55.0;
+        let (minutes, coefficients) = match (event, shafaq) {
+            (PraytimeType::Imsak | PraytimeType::Fajr, _) => (75.0, [28.65, 19.44, 32.74, 48.1]),
+            (_, Shafaq::General) => (75.0, [25.6, 2.05, -9.21, 6.14]),
+            (_, Shafaq::Ahmar) => (62.0, [17.4, -7.16, 5.12, 19.44]),
+            (_, Shafaq::Abyad) => (75.0, [25.6, 7.16, 36.84, 81.84]),
+        };
+        let [a, b, c, d] = coefficients.map(|coefficient| minutes + coefficient * latitude);
+
+        let days = self.days_since_solstice() as f64;
+        match days {
+            days if days < 91.0 => a + (b - a) / 91.0 * days,
+            days if days < 137.0 => b + (c - b) / 46.0 * (days - 91.0),
+            days if days < 183.0 => c + (d - c) / 46.0 * (days - 137.0),
+            days if days < 229.0 => d + (c - d) / 46.0 * (days - 183.0),
+            days if days < 275.0 => c + (b - c) / 46.0 * (days - 229.0),
+            days => b + (a - b) / 91.0 * (days - 275.0),
+        }
+    }
+
+    /// days since the winter solstice of the hemisphere of the location
+    fn days_since_solstice(&self) -> i64 {
+        let day_of_year = self.date.ordinal0() as i64;
+        let days_in_year = if self.date.leap_year() { 366 } else { 365 };
+
+        if self.location.latitude >= 0.0 {
+            (day_of_year + 11).rem_euclid(days_in_year)
+        } else {
+            (day_of_year + 1 - if self.date.leap_year() { 173 } else { 172 })
+                .rem_euclid(days_in_year)
+        }
+    }
+
+    /// portion of the night between the event and its base at 48.5° of latitude
+    fn nearest_latitude_portion(&self, event: PraytimeType, angle: f64) -> f64 {
+        let latitude = NEAREST_LATITUDE.copysign(self.location.latitude);
+        let time = self.twilight_at(event, angle, latitude);
+        let (sunrise, sunset) = self.horizon_at(latitude);
+        let base = match event {
+            PraytimeType::Imsak | PraytimeType::Fajr => sunrise,
+            _ => sunset,
+        };
+        (time - base).abs() / Self::time_difference(sunset, sunrise)
+    }
+
+    /// portion of the night between the event and its base on the nearest day when the event exists, the event
+    /// keeps its place in the night even when the nearest night is much longer
+    ///
+    /// the nearest days are calculated from the default estimates, so a portion only depends on the location, the
+    /// event, its angle and the day, whatever the estimates of the day being refined
+    fn nearest_day_portion(&self, event: PraytimeType, angle: f64) -> f64 {
+        let cache = DayCache::new(self.solar_model);
+        let cache = self.cache.unwrap_or(&cache);
+        // the event, its base and the night of a day, with the solar quantities of some estimates
+        let twilight = |date: &NaiveDate, events: &[PraytimeType]| {
+            let calculator = InternalCalculator::cached(
+                self.params,
+                self.location,
+                date,
+                Estimates::default().only(events),
+                cache,
+            );
+            let (time, base) = calculator.twilight(event, angle);
+            (time, base, calculator.night_time())
+        };
+        let mut portion = |date: NaiveDate| {
+            let key = (event, angle.to_bits(), date);
+            if let Some(portion) = cache.portions.borrow().get(&key) {
+                return *portion;
+            }
+            // the event alone tells whether it exists, sunrise and sunset are only needed on the nearest day
+            let portion = if twilight(&date, &[event]).0.is_nan() {
+                f64::NAN
+            } else {
+                let (time, base, night) =
+                    twilight(&date, &[event, PraytimeType::Sunrise, PraytimeType::Sunset]);
+                (time - base).abs() / night
+            };
+            cache.portions.borrow_mut().insert(key, portion);
+            portion
+        };
+
+        (1..=183)
+            .flat_map(|days| [-days, days])
+            .filter_map(|days| self.date.checked_add_signed(Duration::days(days)))
+            .map(&mut portion)
+            .find(|portion| !portion.is_nan())
+            .unwrap_or(f64::NAN)
+    }
+
     pub(crate) fn night_time(&self) -> f64 {
         Self::time_difference(self.sunset(), self.sunrise())
     }
@@ -197,7 +760,6 @@ impl<'a> InternalCalculator<'a> {
     pub(crate) fn time_difference(time1: f64, time2: f64) -> f64 {
         let difference = time2 - time1;
 
-        let fixed = fix_hour(difference);
-        fixed
+        fix_hour(difference)
     }
 }
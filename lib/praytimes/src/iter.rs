@@ -1,3 +1,24 @@
+//! Iterate over the prayer times of the following ( or previous ) days
+//!
+//! the events come out in chronological order from the start instant, and in reverse order from the end bound with
+//! [`DoubleEndedIterator`]. without an end bound the two directions split at the start instant, so the back of the
+//! iterator walks to the previous prayers.
+//!
+//! ```rust
+//! use chrono::NaiveDate;
+//! use praytimes::{iter::PraytimesIterator, methods, types::{Location, PraytimeType}, Calculator};
+//!
+//! let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+//! let location = Location { latitude: 35.7, longitude: 51.4, ..Default::default() };
+//! let start = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap().and_hms_opt(10, 0, 0).unwrap();
+//!
+//! let mut prayers = PraytimesIterator::new(calculator, location, start)
+//!     .with_types([PraytimeType::Fajr, PraytimeType::Dhuhr]);
+//! assert_eq!(prayers.next().unwrap().0, PraytimeType::Fajr);
+//! assert_eq!(prayers.next_back().unwrap().0, PraytimeType::Dhuhr);
+//! ```
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
 use chrono::{NaiveDate, NaiveDateTime};
 
 use crate::{
@@ -5,28 +26,199 @@ use crate::{
     Calculator,
 };
 
+type Event = (PraytimeType, NaiveDateTime);
+
+/// the order of the events, sunset is before maghrib when they are at the same time
+fn key((kind, time): &Event) -> (NaiveDateTime, PraytimeType) {
+    (*time, *kind)
+}
+
+/// one direction of the iteration
+#[derive(Debug, Clone, Default)]
+struct Cursor {
+    /// last day calculated, `None` before the first calculation
+    date: Option<NaiveDate>,
+    /// the events not returned yet with their day, in order
+    pending: VecDeque<(Event, NaiveDate)>,
+    /// the last event returned in this direction
+    last: Option<Event>,
+    /// no more days to calculate
+    exhausted: bool,
+}
+
+impl Cursor {
+    /// merge the events of another day, the events of a day can be after some events of the next day in high
+    /// latitudes ( like the isha of a day after the imsak of the next )
+    fn merge(&mut self, date: NaiveDate, events: Vec<Event>) {
+        let mut pending: Vec<_> = self
+            .pending
+            .drain(..)
+            .chain(events.into_iter().map(|event| (event, date)))
+            .collect();
+        pending.sort_by_key(|(event, _)| key(event));
+        self.pending = pending.into();
+        self.date = Some(date);
+    }
+}
+/// Iterator over the prayer times of a location, see the [module documentation](self)
+#[derive(Debug, Clone)]
 pub struct PraytimesIterator {
     location: Location,
     calculator: Calculator,
-    date: NaiveDate,
-    calculated: Vec<(PraytimeType, NaiveDateTime)>,
+    start: NaiveDateTime,
+    end: Option<NaiveDateTime>,
+    types: Vec<PraytimeType>,
+    front: Cursor,
+    back: Cursor,
+}
+
+impl PraytimesIterator {
+    /// Iterate over all the events at or after a utc instant
+    pub fn new(calculator: Calculator, location: Location, start: NaiveDateTime) -> Self {
+        use PraytimeType::*;
+        Self {
+            location,
+            calculator,
+            start,
+            end: None,
+            types: vec![
+                Imsak, Fajr, Sunrise, Dhuhr, Asr, Sunset, Maghrib, Isha, Midnight,
+            ],
+            front: Cursor::default(),
+            back: Cursor::default(),
+        }
+    }
+
+    /// Stop before a utc instant, the back of the iterator starts from there
+    pub fn with_end(self, end: NaiveDateTime) -> Self {
+        Self {
+            end: Some(end),
+            ..self
+        }
+    }
+
+    /// Only return the events of some types
+    pub fn with_types(self, types: impl IntoIterator<Item = PraytimeType>) -> Self {
+        Self {
+            types: types.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// the events of a day
+    fn events_of(&self, date: &NaiveDate) -> Vec<Event> {
+        self.calculator
+            .calculate(&self.location, date)
+            .into_vec()
+            .into_iter()
+            .filter(|(kind, _)| self.types.contains(kind))
+            .collect()
+    }
 }
 
 impl Iterator for PraytimesIterator {
-    type Item = (PraytimeType, NaiveDateTime);
+    type Item = Event;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.calculated.len() == 0 {
-            match self.date.succ_opt() {
-                Some(d) => {
-                    let times = self.calculator.calculate(&self.location, &d).into_vec();
-                    self.date = d;
-                    self.calculated = times;
+        if self.types.is_empty() {
+            return None;
+        }
+        // the events of a day can fall on the utc day before, for eastern longitudes
+        let last_date = match self.end {
+            Some(end) => Some(end.date().succ_opt()?),
+            None => None,
+        };
+        loop {
+            // an event is in order once the events of the next day are merged
+            let ready = self
+                .front
+                .pending
+                .front()
+                .is_some_and(|(_, day)| self.front.date.is_some_and(|date| *day < date));
+            if !ready && !self.front.exhausted {
+                let date = match self.front.date {
+                    Some(date) => date.succ_opt(),
+                    None => self.start.date().pred_opt(),
+                };
+                match date.filter(|date| last_date.is_none_or(|last_date| *date <= last_date)) {
+                    Some(date) => {
+                        let events = self.events_of(&date);
+                        self.front.merge(date, events);
+                    }
+                    None => self.front.exhausted = true,
                 }
-                None => return None,
+                continue;
+            }
+            let (event, _) = self.front.pending.pop_front()?;
+
+            if event.1 < self.start {
+                continue;
             }
+            if self.end.is_some_and(|end| event.1 >= end) {
+                return None;
+            }
+            // meeting the back of the iterator
+            let met = self.back.last.is_some_and(|back| key(&event) >= key(&back));
+            if self.end.is_some() && met {
+                return None;
+            }
+            self.front.last = Some(event);
+            return Some(event);
+        }
+    }
+}
+
+impl DoubleEndedIterator for PraytimesIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.types.is_empty() {
+            return None;
         }
+        let upper = self.end.unwrap_or(self.start);
+        let first_date = match self.end {
+            Some(_) => Some(self.start.date().pred_opt()?),
+            None => None,
+        };
+        loop {
+            // an event is in order once the events of the day before are merged
+            let ready = self
+                .back
+                .pending
+                .back()
+                .is_some_and(|(_, day)| self.back.date.is_some_and(|date| *day > date));
+            if !ready && !self.back.exhausted {
+                let date = match self.back.date {
+                    Some(date) => date.pred_opt(),
+                    None => upper.date().succ_opt(),
+                };
+                match date.filter(|date| first_date.is_none_or(|first_date| *date >= first_date)) {
+                    Some(date) => {
+                        let events = self.events_of(&date);
+                        self.back.merge(date, events);
+                    }
+                    None => self.back.exhausted = true,
+                }
+                continue;
+            }
+            let (event, _) = self.back.pending.pop_back()?;
 
-        self.calculated.pop()
+            if event.1 >= upper {
+                continue;
+            }
+            if self.end.is_some() {
+                if event.1 < self.start {
+                    return None;
+                }
+                // meeting the front of the iterator
+                if self
+                    .front
+                    .last
+                    .is_some_and(|front| key(&event) <= key(&front))
+                {
+                    return None;
+                }
+            }
+            self.back.last = Some(event);
+            return Some(event);
+        }
     }
 }
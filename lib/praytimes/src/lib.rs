@@ -3,12 +3,37 @@
 //! for information about the calculation see [calculation](http://praytimes.org/calculation)
 //!
 //! see [`Calculator`] for calculation
+//!
+//! the `std` feature is enabled by default, without it the crate is `no_std` ( with `alloc` ) and the float math
+//! comes from the `libm` feature. see [`Calculator::calculate_hours`] to calculate from a julian day in
+//! fractional hours. without `std` the calculator shares its solar model in an [`alloc::rc::Rc`], so it builds on the
+//! targets without atomic pointers like thumbv6m, but it isn't `Send` there
+// without `std` nor `libm` the float math of std is kept, so only the error below is reported
+#![cfg_attr(all(not(feature = "std"), feature = "libm"), no_std)]
+
+extern crate alloc;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("the float math needs the `std` or the `libm` feature");
+
+pub mod current;
+pub mod error;
+pub mod forbidden;
+pub mod hijri;
 mod internal_calculator;
+pub mod lunar;
 pub mod methods;
+pub mod qibla;
+pub mod solar;
+pub mod timetable;
 pub mod types;
 mod utils;
+pub mod voluntary;
+pub mod windows;
 
 mod calculator;
 
 pub use calculator::Calculator;
 pub mod iter;
+#[cfg(feature = "chrono-tz")]
+pub mod zoned;
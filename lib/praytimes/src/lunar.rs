@@ -0,0 +1,265 @@
+//! Crescent visibility on the evening after the conjunction
+//!
+//! the new month begins with the first sighting of the crescent after sunset, the report of an evening holds the
+//! geometry of the moon at the best time to see it ( sunset + 4/9 of the lag, see Yallop ) and its
+//! classification by the criteria of Yallop ( NAO Technical Note 69, 1997 ) and Odeh ( Experimental Astronomy 18, 2004 ).
+//!
+//! ```rust
+//! use chrono::NaiveDate;
+//! use praytimes::{lunar::{self, YallopZone}, solar::Almanac, types::Location};
+//!
+//! let tehran = Location {
+//!     latitude: 35.69,
+//!     longitude: 51.39,
+//!     ..Default::default()
+//! };
+//! // the evening after the conjunction of 2023-03-21 17:23 utc
+//! let date = NaiveDate::from_ymd_opt(2023, 3, 22).unwrap();
+//! let report = lunar::crescent_visibility(&tehran, &date, &Almanac).unwrap();
+//!
+//! assert!(report.age > 20.0);
+//! assert_eq!(report.yallop.zone, YallopZone::B);
+//! ```
+use chrono::{NaiveDate, NaiveDateTime};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+use crate::utils::float::Float;
+use crate::internal_calculator::{Estimates, InternalCalculator};
+use crate::methods;
+use crate::solar::{true_horizontal, SolarModel};
+use crate::types::Location;
+use crate::utils::{
+    d_math::{arccos, cos, sin},
+    julian_date::{datetime_from_julian, julian_from_datetime},
+    moon::{lunation_near, moon_horizon_altitude, moon_horizontal, moon_position, new_moon},
+};
+
+/// visibility zones of Yallop's q-test
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum YallopZone {
+    /// easily visible to the naked eye ( q > 0.216 )
+    A,
+    /// visible under perfect conditions ( q > -0.014 )
+    B,
+    /// may need optical aid to find the crescent before seeing it with the naked eye ( q > -0.160 )
+    C,
+    /// will need optical aid to find the crescent ( q > -0.232 )
+    D,
+    /// not visible with a telescope ( q > -0.293 )
+    E,
+    /// not visible, below the Danjon limit
+    F,
+}
+
+impl YallopZone {
+    /// zone of a q-value
+    pub fn from_q(q: f64) -> Self {
+        match q {
+            q if q > 0.216 => YallopZone::A,
+            q if q > -0.014 => YallopZone::B,
+            q if q > -0.160 => YallopZone::C,
+            q if q > -0.232 => YallopZone::D,
+            q if q > -0.293 => YallopZone::E,
+            _ => YallopZone::F,
+        }
+    }
+}
+
+/// visibility zones of Odeh's criterion
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum OdehZone {
+    /// visible by the naked eye ( V ≥ 5.65 )
+    A,
+    /// visible by optical aid, could be seen by the naked eye ( V ≥ 2.00 )
+    B,
+    /// visible by optical aid only ( V ≥ -0.96 )
+    C,
+    /// not visible even by optical aid
+    D,
+}
+
+impl OdehZone {
+    /// zone of a V-value
+    pub fn from_v(v: f64) -> Self {
+        match v {
+            v if v >= 5.65 => OdehZone::A,
+            v if v >= 2.00 => OdehZone::B,
+            v if v >= -0.96 => OdehZone::C,
+            _ => OdehZone::D,
+        }
+    }
+}
+
+/// Yallop's q-value and its zone
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Yallop {
+    pub q: f64,
+    pub zone: YallopZone,
+}
+
+/// Odeh's V-value and its zone
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Odeh {
+    pub v: f64,
+    pub zone: OdehZone,
+}
+
+/// crescent visibility report of an evening, times are in utc and angles in degrees
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrescentVisibility {
+    /// the closest conjunction ( new moon ) to the sunset
+    pub conjunction: NaiveDateTime,
+    pub sunset: NaiveDateTime,
+    pub moonset: NaiveDateTime,
+
+    /// hours from the conjunction to the sunset, negative when the conjunction is after the sunset
+    pub age: f64,
+
+    /// minutes from the sunset to the moonset, negative when the moon sets first
+    pub lag: f64,
+
+    /// best time to see the crescent, sunset + 4/9 of the lag
+    pub best_time: NaiveDateTime,
+
+    /// geocentric elongation of the moon from the sun ( ARCL )
+    pub elongation: f64,
+
+    /// geocentric difference in altitude between the moon and the sun ( ARCV )
+    pub arc_of_vision: f64,
+
+    /// difference in azimuth between the sun and the moon ( DAZ )
+    pub relative_azimuth: f64,
+
+    /// topocentric width of the crescent in arc minutes
+    pub width: f64,
+
+    pub yallop: Yallop,
+    pub odeh: Odeh,
+}
+
+/// The conjunction ( new moon ) closest to an instant
+pub fn conjunction_near(time: &NaiveDateTime) -> Option<NaiveDateTime> {
+    datetime_from_julian(new_moon(lunation_near(julian_from_datetime(time))))
+}
+
+/// The crescent visibility on the evening of a day, at a location
+///
+/// `None` when the sun or the moon doesn't set that evening ( in high latitudes )
+pub fn crescent_visibility(
+    location: &Location,
+    date: &NaiveDate,
+    solar_model: &dyn SolarModel,
+) -> Option<CrescentVisibility> {
+    let sunset = sunset(location, date, solar_model)?;
+    let moonset = moonset(location, sunset)?;
+    let best_time = sunset + 4.0 / 9.0 * (moonset - sunset);
+    let conjunction = new_moon(lunation_near(sunset));
+
+    let (sun_altitude, sun_azimuth) = true_horizontal(solar_model, location, best_time);
+    let (moon_altitude, moon_azimuth) =
+        moon_horizontal(best_time, location.latitude, location.longitude);
+    let relative_azimuth = sun_azimuth - moon_azimuth;
+
+    let moon = moon_position(best_time);
+    let parallax = moon.parallax();
+    let topocentric_altitude = moon_altitude - parallax * cos(moon_altitude);
+    let semi_diameter = moon.semi_diameter() * (1.0 + sin(moon_altitude) * sin(parallax));
+
+    let elongation = arc_length(sun_altitude, moon_altitude, relative_azimuth);
+    let topocentric_elongation = arc_length(sun_altitude, topocentric_altitude, relative_azimuth);
+
+    let arc_of_vision = moon_altitude - sun_altitude;
+    let yallop_width = semi_diameter * 60.0 * (1.0 - cos(elongation));
+    let width = semi_diameter * 60.0 * (1.0 - cos(topocentric_elongation));
+
+    let q = (arc_of_vision - (11.8371 - width_polynomial(yallop_width))) / 10.0;
+    let v = topocentric_altitude - sun_altitude - (7.1651 - width_polynomial(width));
+
+    Some(CrescentVisibility {
+        conjunction: datetime_from_julian(conjunction)?,
+        sunset: datetime_from_julian(sunset)?,
+        moonset: datetime_from_julian(moonset)?,
+        age: (sunset - conjunction) * 24.0,
+        lag: (moonset - sunset) * 24.0 * 60.0,
+        best_time: datetime_from_julian(best_time)?,
+        elongation,
+        arc_of_vision,
+        relative_azimuth,
+        width,
+        yallop: Yallop {
+            q,
+            zone: YallopZone::from_q(q),
+        },
+        odeh: Odeh {
+            v,
+            zone: OdehZone::from_v(v),
+        },
+    })
+}
+
+/// the terms of the width shared by the two criteria
+fn width_polynomial(width: f64) -> f64 {
+    6.3226 * width - 0.7319 * width.powi(2) + 0.1018 * width.powi(3)
+}
+
+/// angular distance between two bodies from their altitudes and difference in azimuth
+fn arc_length(altitude_1: f64, altitude_2: f64, relative_azimuth: f64) -> f64 {
+    arccos(
+        sin(altitude_1) * sin(altitude_2)
+            + cos(altitude_1) * cos(altitude_2) * cos(relative_azimuth),
+    )
+}
+
+/// julian date of the sunset, refined to the second
+fn sunset(location: &Location, date: &NaiveDate, solar_model: &dyn SolarModel) -> Option<f64> {
+    let mut calculator = InternalCalculator::new(
+        &methods::MUSLIM_WORLD_LEAGUE,
+        solar_model,
+        location,
+        date,
+        Estimates::default(),
+    );
+    for _ in 0..2 {
+        calculator = calculator.with_estimates(calculator.refined_estimates());
+    }
+    calculator
+        .datetime_from_hours(calculator.sunset())
+        .map(|sunset| julian_from_datetime(&sunset))
+}
+
+/// julian date of the moonset closest to the sunset, within half a day
+fn moonset(location: &Location, sunset: f64) -> Option<f64> {
+    let above_horizon = |jd: f64| {
+        moon_horizontal(jd, location.latitude, location.longitude).0 > moon_horizon_altitude(jd)
+    };
+    // the moon sets after the sunset when it's still up, search forward and backward otherwise
+    let step = if above_horizon(sunset) { 1.0 } else { -1.0 } / 144.0;
+
+    let crossed = (1..=72)
+        .map(|i| sunset + step * i as f64)
+        .find(|jd| above_horizon(*jd) != above_horizon(sunset))?;
+
+    // `before` is above the horizon and `after` below
+    let (mut before, mut after) = if step > 0.0 {
+        (crossed - step, crossed)
+    } else {
+        (crossed, crossed - step)
+    };
+    for _ in 0..30 {
+        let middle = (before + after) / 2.0;
+        if above_horizon(middle) {
+            before = middle;
+        } else {
+            after = middle;
+        }
+    }
+    Some((before + after) / 2.0)
+}
@@ -1,16 +1,30 @@
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
 use crate::types::{
-    AsrFactor, CalculationUnit, Degrees, HighLatsMethod, MidnightMethod, Minutes, Parameters,
+    AsrFactor, CalculationUnit, Degrees, HighLatsMethod, HijriRule, Ihtiyat, MidnightMethod,
+    Minutes, Parameters, PraytimeType, Rounding, RoundingPolicy, Seasonal, Shafaq,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 pub const MUSLIM_WORLD_LEAGUE: Parameters = Parameters {
     imsak: CalculationUnit::Minutes(Minutes { minutes: 10.0 }),
     dhuhr: Minutes { minutes: 0.0 },
     asr: AsrFactor { factor: 1.0 },
     high_latitudes: HighLatsMethod::NightMiddle,
-    fajr: (Degrees { degree: 18.0 }),
+    fajr: CalculationUnit::Degrees(Degrees { degree: 18.0 }),
     isha: CalculationUnit::Degrees(Degrees { degree: 17.0 }),
     midnight: MidnightMethod::Standard,
     maghrib: CalculationUnit::Minutes(Minutes { minutes: 0.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::NONE,
 };
 
 pub const ISLAMIC_SOCIETY_OF_NORTH_AMERICA: Parameters = Parameters {
@@ -18,10 +32,13 @@ pub const ISLAMIC_SOCIETY_OF_NORTH_AMERICA: Parameters = Parameters {
     dhuhr: Minutes { minutes: 0.0 },
     asr: AsrFactor { factor: 1.0 },
     high_latitudes: HighLatsMethod::NightMiddle,
-    fajr: (Degrees { degree: 15.0 }),
+    fajr: CalculationUnit::Degrees(Degrees { degree: 15.0 }),
     isha: CalculationUnit::Degrees(Degrees { degree: 15.0 }),
     midnight: MidnightMethod::Standard,
     maghrib: CalculationUnit::Minutes(Minutes { minutes: 0.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::NONE,
 };
 
 pub const EGYPTIAN_GENERAL_AUTHORITY_OF_SURVEY: Parameters = Parameters {
@@ -29,21 +46,32 @@ pub const EGYPTIAN_GENERAL_AUTHORITY_OF_SURVEY: Parameters = Parameters {
     dhuhr: Minutes { minutes: 0.0 },
     asr: AsrFactor { factor: 1.0 },
     high_latitudes: HighLatsMethod::NightMiddle,
-    fajr: (Degrees { degree: 19.5 }),
+    fajr: CalculationUnit::Degrees(Degrees { degree: 19.5 }),
     isha: CalculationUnit::Degrees(Degrees { degree: 17.5 }),
     midnight: MidnightMethod::Standard,
     maghrib: CalculationUnit::Minutes(Minutes { minutes: 0.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::NONE,
 };
 
+/// Umm al-Qura University, isha 90 minutes after maghrib and 120 minutes in ramadan
 pub const UMM_AL_QURA_UNIVERSITY_MAKKAH: Parameters = Parameters {
     imsak: CalculationUnit::Minutes(Minutes { minutes: 10.0 }),
     dhuhr: Minutes { minutes: 0.0 },
     asr: AsrFactor { factor: 1.0 },
     high_latitudes: HighLatsMethod::NightMiddle,
-    fajr: (Degrees { degree: 18.5 }),
+    fajr: CalculationUnit::Degrees(Degrees { degree: 18.5 }),
     isha: CalculationUnit::Minutes(Minutes { minutes: 90.0 }),
     midnight: MidnightMethod::Standard,
     maghrib: CalculationUnit::Minutes(Minutes { minutes: 0.0 }),
+    hijri_rules: Cow::Borrowed(&[HijriRule {
+        month: 9,
+        event: PraytimeType::Isha,
+        unit: CalculationUnit::Minutes(Minutes { minutes: 120.0 }),
+    }]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::NONE,
 };
 
 pub const UNIVERSITY_OF_ISLAMIC_SCIENCES_KARACHI: Parameters = Parameters {
@@ -51,10 +79,13 @@ pub const UNIVERSITY_OF_ISLAMIC_SCIENCES_KARACHI: Parameters = Parameters {
     dhuhr: Minutes { minutes: 0.0 },
     asr: AsrFactor { factor: 1.0 },
     high_latitudes: HighLatsMethod::NightMiddle,
-    fajr: (Degrees { degree: 18.0 }),
+    fajr: CalculationUnit::Degrees(Degrees { degree: 18.0 }),
     isha: CalculationUnit::Degrees(Degrees { degree: 18.0 }),
     midnight: MidnightMethod::Standard,
     maghrib: CalculationUnit::Minutes(Minutes { minutes: 0.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::NONE,
 };
 
 pub const INSTITUTE_OF_GEOPHYSICS_UNIVERSITY_OF_TEHRAN: Parameters = Parameters {
@@ -62,10 +93,13 @@ pub const INSTITUTE_OF_GEOPHYSICS_UNIVERSITY_OF_TEHRAN: Parameters = Parameters
     dhuhr: Minutes { minutes: 0.0 },
     asr: AsrFactor { factor: 1.0 },
     high_latitudes: HighLatsMethod::NightMiddle,
-    fajr: (Degrees { degree: 17.7 }),
+    fajr: CalculationUnit::Degrees(Degrees { degree: 17.7 }),
     isha: CalculationUnit::Degrees(Degrees { degree: 14.0 }),
     midnight: MidnightMethod::Jafari,
     maghrib: CalculationUnit::Degrees(Degrees { degree: 4.5 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::NONE,
 };
 
 pub const SHIA_ITHNA_ASHARI_LEVA_INSTITUTE_QUM: Parameters = Parameters {
@@ -73,29 +107,529 @@ pub const SHIA_ITHNA_ASHARI_LEVA_INSTITUTE_QUM: Parameters = Parameters {
     dhuhr: Minutes { minutes: 0.0 },
     asr: AsrFactor { factor: 1.0 },
     high_latitudes: HighLatsMethod::NightMiddle,
-    fajr: (Degrees { degree: 16.0 }),
+    fajr: CalculationUnit::Degrees(Degrees { degree: 16.0 }),
     isha: CalculationUnit::Degrees(Degrees { degree: 14.0 }),
     midnight: MidnightMethod::Jafari,
     maghrib: CalculationUnit::Degrees(Degrees { degree: 4.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::NONE,
 };
 
-pub fn get_method_by_name(name: &str) -> Option<Parameters> {
-    match name {
-        "MUSLIM_WORLD_LEAGUE" | "MWL" => Some(MUSLIM_WORLD_LEAGUE),
-        "ISLAMIC_SOCIETY_OF_NORTH_AMERICA" | "ISNA" => Some(ISLAMIC_SOCIETY_OF_NORTH_AMERICA),
-        "EGYPTIAN_GENERAL_AUTHORITY_OF_SURVEY" | "Egypt" => {
-            Some(EGYPTIAN_GENERAL_AUTHORITY_OF_SURVEY)
-        }
-        "UMM_AL_QURA_UNIVERSITY_MAKKAH" | "Makkah" => Some(UMM_AL_QURA_UNIVERSITY_MAKKAH),
-        "UNIVERSITY_OF_ISLAMIC_SCIENCES_KARACHI" | "Karachi" => {
-            Some(UNIVERSITY_OF_ISLAMIC_SCIENCES_KARACHI)
+/// Moonsighting Committee Worldwide, fajr and isha at 18° limited by the seasonal times
+/// ( use [`Parameters::with_shafaq`] for the ahmar or abyad isha )
+pub const MOONSIGHTING_COMMITTEE_WORLDWIDE: Parameters = Parameters {
+    imsak: CalculationUnit::Minutes(Minutes { minutes: 10.0 }),
+    dhuhr: Minutes { minutes: 5.0 },
+    asr: AsrFactor { factor: 1.0 },
+    high_latitudes: HighLatsMethod::MoonsightingCommittee,
+    fajr: CalculationUnit::Seasonal(Seasonal {
+        degree: 18.0,
+        shafaq: Shafaq::General,
+    }),
+    isha: CalculationUnit::Seasonal(Seasonal {
+        degree: 18.0,
+        shafaq: Shafaq::General,
+    }),
+    midnight: MidnightMethod::Standard,
+    maghrib: CalculationUnit::Minutes(Minutes { minutes: 3.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::NONE,
+};
+
+/// Gulf Region, isha 90 minutes after maghrib
+pub const GULF_REGION: Parameters = Parameters {
+    imsak: CalculationUnit::Minutes(Minutes { minutes: 10.0 }),
+    dhuhr: Minutes { minutes: 0.0 },
+    asr: AsrFactor { factor: 1.0 },
+    high_latitudes: HighLatsMethod::NightMiddle,
+    fajr: CalculationUnit::Degrees(Degrees { degree: 19.5 }),
+    isha: CalculationUnit::Minutes(Minutes { minutes: 90.0 }),
+    midnight: MidnightMethod::Standard,
+    maghrib: CalculationUnit::Minutes(Minutes { minutes: 0.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::NONE,
+};
+
+/// Ministry of Awqaf and Islamic Affairs, Kuwait
+pub const KUWAIT: Parameters = Parameters {
+    imsak: CalculationUnit::Minutes(Minutes { minutes: 10.0 }),
+    dhuhr: Minutes { minutes: 0.0 },
+    asr: AsrFactor { factor: 1.0 },
+    high_latitudes: HighLatsMethod::NightMiddle,
+    fajr: CalculationUnit::Degrees(Degrees { degree: 18.0 }),
+    isha: CalculationUnit::Degrees(Degrees { degree: 17.5 }),
+    midnight: MidnightMethod::Standard,
+    maghrib: CalculationUnit::Minutes(Minutes { minutes: 0.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::NONE,
+};
+
+/// Ministry of Awqaf and Islamic Affairs, Qatar, isha 90 minutes after maghrib
+pub const QATAR: Parameters = Parameters {
+    imsak: CalculationUnit::Minutes(Minutes { minutes: 10.0 }),
+    dhuhr: Minutes { minutes: 0.0 },
+    asr: AsrFactor { factor: 1.0 },
+    high_latitudes: HighLatsMethod::NightMiddle,
+    fajr: CalculationUnit::Degrees(Degrees { degree: 18.0 }),
+    isha: CalculationUnit::Minutes(Minutes { minutes: 90.0 }),
+    midnight: MidnightMethod::Standard,
+    maghrib: CalculationUnit::Minutes(Minutes { minutes: 0.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::NONE,
+};
+
+/// Islamic Affairs and Charitable Activities Department, Dubai, with 3 minutes of ihtiyat
+pub const DUBAI: Parameters = Parameters {
+    imsak: CalculationUnit::Minutes(Minutes { minutes: 10.0 }),
+    dhuhr: Minutes { minutes: 3.0 },
+    asr: AsrFactor { factor: 1.0 },
+    high_latitudes: HighLatsMethod::NightMiddle,
+    fajr: CalculationUnit::Degrees(Degrees { degree: 18.2 }),
+    isha: CalculationUnit::Degrees(Degrees { degree: 18.2 }),
+    midnight: MidnightMethod::Standard,
+    maghrib: CalculationUnit::Minutes(Minutes { minutes: 3.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat {
+        sunrise: -3.0,
+        asr: 3.0,
+        ..Ihtiyat::NONE
+    },
+    rounding: RoundingPolicy::NONE,
+};
+
+/// Majlis Ugama Islam Singapura ( MUIS ), Singapore, the times are rounded up to the minute
+pub const MAJLIS_UGAMA_ISLAM_SINGAPURA: Parameters = Parameters {
+    imsak: CalculationUnit::Minutes(Minutes { minutes: 10.0 }),
+    dhuhr: Minutes { minutes: 1.0 },
+    asr: AsrFactor { factor: 1.0 },
+    high_latitudes: HighLatsMethod::NightMiddle,
+    fajr: CalculationUnit::Degrees(Degrees { degree: 20.0 }),
+    isha: CalculationUnit::Degrees(Degrees { degree: 18.0 }),
+    midnight: MidnightMethod::Standard,
+    maghrib: CalculationUnit::Minutes(Minutes { minutes: 0.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::all(Rounding::Ceil),
+};
+
+/// Jabatan Kemajuan Islam Malaysia ( JAKIM ), Malaysia, the times are rounded up to the minute
+pub const JABATAN_KEMAJUAN_ISLAM_MALAYSIA: Parameters = Parameters {
+    imsak: CalculationUnit::Minutes(Minutes { minutes: 10.0 }),
+    dhuhr: Minutes { minutes: 0.0 },
+    asr: AsrFactor { factor: 1.0 },
+    high_latitudes: HighLatsMethod::NightMiddle,
+    fajr: CalculationUnit::Degrees(Degrees { degree: 20.0 }),
+    isha: CalculationUnit::Degrees(Degrees { degree: 18.0 }),
+    midnight: MidnightMethod::Standard,
+    maghrib: CalculationUnit::Minutes(Minutes { minutes: 0.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::all(Rounding::Ceil),
+};
+
+/// Kementerian Agama Republik Indonesia ( Kemenag ), Indonesia, with 2 minutes of ihtiyat
+pub const KEMENTERIAN_AGAMA_INDONESIA: Parameters = Parameters {
+    imsak: CalculationUnit::Minutes(Minutes { minutes: 10.0 }),
+    dhuhr: Minutes { minutes: 0.0 },
+    asr: AsrFactor { factor: 1.0 },
+    high_latitudes: HighLatsMethod::NightMiddle,
+    fajr: CalculationUnit::Degrees(Degrees { degree: 20.0 }),
+    isha: CalculationUnit::Degrees(Degrees { degree: 18.0 }),
+    midnight: MidnightMethod::Standard,
+    maghrib: CalculationUnit::Minutes(Minutes { minutes: 0.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat {
+        imsak: 2.0,
+        fajr: 2.0,
+        sunrise: -2.0,
+        dhuhr: 2.0,
+        asr: 2.0,
+        maghrib: 2.0,
+        isha: 2.0,
+        ..Ihtiyat::NONE
+    },
+    rounding: RoundingPolicy::NONE,
+};
+
+/// Diyanet İşleri Başkanlığı, Turkey, with the temkin minutes of its tables rounded to the minute
+pub const DIYANET_ISLERI_BASKANLIGI: Parameters = Parameters {
+    imsak: CalculationUnit::Minutes(Minutes { minutes: 10.0 }),
+    dhuhr: Minutes { minutes: 0.0 },
+    asr: AsrFactor { factor: 1.0 },
+    high_latitudes: HighLatsMethod::NightMiddle,
+    fajr: CalculationUnit::Degrees(Degrees { degree: 18.0 }),
+    isha: CalculationUnit::Degrees(Degrees { degree: 17.0 }),
+    midnight: MidnightMethod::Standard,
+    maghrib: CalculationUnit::Minutes(Minutes { minutes: 0.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat {
+        sunrise: -7.0,
+        dhuhr: 5.0,
+        asr: 4.0,
+        maghrib: 7.0,
+        ..Ihtiyat::NONE
+    },
+    rounding: RoundingPolicy::all(Rounding::Nearest),
+};
+
+/// Union des Organisations Islamiques de France ( UOIF ), France
+pub const UNION_DES_ORGANISATIONS_ISLAMIQUES_DE_FRANCE: Parameters = Parameters {
+    imsak: CalculationUnit::Minutes(Minutes { minutes: 10.0 }),
+    dhuhr: Minutes { minutes: 0.0 },
+    asr: AsrFactor { factor: 1.0 },
+    high_latitudes: HighLatsMethod::NightMiddle,
+    fajr: CalculationUnit::Degrees(Degrees { degree: 12.0 }),
+    isha: CalculationUnit::Degrees(Degrees { degree: 12.0 }),
+    midnight: MidnightMethod::Standard,
+    maghrib: CalculationUnit::Minutes(Minutes { minutes: 0.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::NONE,
+};
+
+/// Spiritual Administration of Muslims of Russia
+pub const SPIRITUAL_ADMINISTRATION_OF_MUSLIMS_OF_RUSSIA: Parameters = Parameters {
+    imsak: CalculationUnit::Minutes(Minutes { minutes: 10.0 }),
+    dhuhr: Minutes { minutes: 0.0 },
+    asr: AsrFactor { factor: 1.0 },
+    high_latitudes: HighLatsMethod::NightMiddle,
+    fajr: CalculationUnit::Degrees(Degrees { degree: 16.0 }),
+    isha: CalculationUnit::Degrees(Degrees { degree: 15.0 }),
+    midnight: MidnightMethod::Standard,
+    maghrib: CalculationUnit::Minutes(Minutes { minutes: 0.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::NONE,
+};
+
+/// Ministry of Habous and Islamic Affairs, Morocco
+pub const MINISTRY_OF_HABOUS_AND_ISLAMIC_AFFAIRS_MOROCCO: Parameters = Parameters {
+    imsak: CalculationUnit::Minutes(Minutes { minutes: 10.0 }),
+    dhuhr: Minutes { minutes: 5.0 },
+    asr: AsrFactor { factor: 1.0 },
+    high_latitudes: HighLatsMethod::NightMiddle,
+    fajr: CalculationUnit::Degrees(Degrees { degree: 19.0 }),
+    isha: CalculationUnit::Degrees(Degrees { degree: 17.0 }),
+    midnight: MidnightMethod::Standard,
+    maghrib: CalculationUnit::Minutes(Minutes { minutes: 5.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::NONE,
+};
+
+/// Ministry of Religious Affairs and Wakfs, Algeria
+pub const MINISTRY_OF_RELIGIOUS_AFFAIRS_ALGERIA: Parameters = Parameters {
+    imsak: CalculationUnit::Minutes(Minutes { minutes: 10.0 }),
+    dhuhr: Minutes { minutes: 0.0 },
+    asr: AsrFactor { factor: 1.0 },
+    high_latitudes: HighLatsMethod::NightMiddle,
+    fajr: CalculationUnit::Degrees(Degrees { degree: 18.0 }),
+    isha: CalculationUnit::Degrees(Degrees { degree: 17.0 }),
+    midnight: MidnightMethod::Standard,
+    maghrib: CalculationUnit::Minutes(Minutes { minutes: 3.0 }),
+    hijri_rules: Cow::Borrowed(&[]),
+    ihtiyat: Ihtiyat::NONE,
+    rounding: RoundingPolicy::NONE,
+};
+
+/// A calculation method, its parameters and where it's used
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Method {
+    /// unique identifier, the name of the constant for the built-in methods
+    pub id: String,
+    /// human readable name
+    pub name: String,
+    /// short names also accepted by [`MethodRegistry::get`]
+    pub aliases: Vec<String>,
+    /// where the method is commonly used
+    pub region: String,
+    /// the institution which defines the method
+    pub authority: String,
+    pub parameters: Parameters,
+}
+
+/// metadata of the methods defined in this module
+struct BuiltinMethod {
+    id: &'static str,
+    name: &'static str,
+    aliases: &'static [&'static str],
+    region: &'static str,
+    authority: &'static str,
+    parameters: Parameters,
+}
+
+const BUILTIN_METHODS: &[BuiltinMethod] = &[
+    BuiltinMethod {
+        id: "MUSLIM_WORLD_LEAGUE",
+        name: "Muslim World League",
+        aliases: &["MWL"],
+        region: "Europe, Far East, parts of America",
+        authority: "Muslim World League",
+        parameters: MUSLIM_WORLD_LEAGUE,
+    },
+    BuiltinMethod {
+        id: "ISLAMIC_SOCIETY_OF_NORTH_AMERICA",
+        name: "Islamic Society of North America",
+        aliases: &["ISNA"],
+        region: "North America",
+        authority: "Islamic Society of North America",
+        parameters: ISLAMIC_SOCIETY_OF_NORTH_AMERICA,
+    },
+    BuiltinMethod {
+        id: "EGYPTIAN_GENERAL_AUTHORITY_OF_SURVEY",
+        name: "Egyptian General Authority of Survey",
+        aliases: &["Egypt"],
+        region: "Africa, Syria, Lebanon, Malaysia",
+        authority: "Egyptian General Authority of Survey",
+        parameters: EGYPTIAN_GENERAL_AUTHORITY_OF_SURVEY,
+    },
+    BuiltinMethod {
+        id: "UMM_AL_QURA_UNIVERSITY_MAKKAH",
+        name: "Umm al-Qura, Makkah",
+        aliases: &["Makkah"],
+        region: "Arabian Peninsula",
+        authority: "Umm al-Qura University, Makkah",
+        parameters: UMM_AL_QURA_UNIVERSITY_MAKKAH,
+    },
+    BuiltinMethod {
+        id: "UNIVERSITY_OF_ISLAMIC_SCIENCES_KARACHI",
+        name: "University of Islamic Sciences, Karachi",
+        aliases: &["Karachi"],
+        region: "Pakistan, Afghanistan, Bangladesh, India",
+        authority: "University of Islamic Sciences, Karachi",
+        parameters: UNIVERSITY_OF_ISLAMIC_SCIENCES_KARACHI,
+    },
+    BuiltinMethod {
+        id: "INSTITUTE_OF_GEOPHYSICS_UNIVERSITY_OF_TEHRAN",
+        name: "Institute of Geophysics, University of Tehran",
+        aliases: &["Tehran"],
+        region: "Iran",
+        authority: "Institute of Geophysics, University of Tehran",
+        parameters: INSTITUTE_OF_GEOPHYSICS_UNIVERSITY_OF_TEHRAN,
+    },
+    BuiltinMethod {
+        id: "SHIA_ITHNA_ASHARI_LEVA_INSTITUTE_QUM",
+        name: "Shia Ithna-Ashari, Leva Institute, Qum",
+        aliases: &["Jafari"],
+        region: "Shia communities",
+        authority: "Leva Institute, Qum",
+        parameters: SHIA_ITHNA_ASHARI_LEVA_INSTITUTE_QUM,
+    },
+    BuiltinMethod {
+        id: "MOONSIGHTING_COMMITTEE_WORLDWIDE",
+        name: "Moonsighting Committee Worldwide",
+        aliases: &["MoonsightingCommittee"],
+        region: "Worldwide",
+        authority: "Moonsighting Committee Worldwide ( Khalid Shaukat )",
+        parameters: MOONSIGHTING_COMMITTEE_WORLDWIDE,
+    },
+    BuiltinMethod {
+        id: "GULF_REGION",
+        name: "Gulf Region",
+        aliases: &["Gulf"],
+        region: "Gulf",
+        authority: "Gulf Region",
+        parameters: GULF_REGION,
+    },
+    BuiltinMethod {
+        id: "KUWAIT",
+        name: "Kuwait",
+        aliases: &[],
+        region: "Kuwait",
+        authority: "Ministry of Awqaf and Islamic Affairs, Kuwait",
+        parameters: KUWAIT,
+    },
+    BuiltinMethod {
+        id: "QATAR",
+        name: "Qatar",
+        aliases: &[],
+        region: "Qatar",
+        authority: "Ministry of Awqaf and Islamic Affairs, Qatar",
+        parameters: QATAR,
+    },
+    BuiltinMethod {
+        id: "DUBAI",
+        name: "Dubai",
+        aliases: &[],
+        region: "United Arab Emirates",
+        authority: "Islamic Affairs and Charitable Activities Department, Dubai",
+        parameters: DUBAI,
+    },
+    BuiltinMethod {
+        id: "MAJLIS_UGAMA_ISLAM_SINGAPURA",
+        name: "Majlis Ugama Islam Singapura",
+        aliases: &["Singapore", "MUIS"],
+        region: "Singapore",
+        authority: "Majlis Ugama Islam Singapura",
+        parameters: MAJLIS_UGAMA_ISLAM_SINGAPURA,
+    },
+    BuiltinMethod {
+        id: "JABATAN_KEMAJUAN_ISLAM_MALAYSIA",
+        name: "Jabatan Kemajuan Islam Malaysia",
+        aliases: &["Malaysia", "JAKIM"],
+        region: "Malaysia",
+        authority: "Jabatan Kemajuan Islam Malaysia",
+        parameters: JABATAN_KEMAJUAN_ISLAM_MALAYSIA,
+    },
+    BuiltinMethod {
+        id: "KEMENTERIAN_AGAMA_INDONESIA",
+        name: "Kementerian Agama Republik Indonesia",
+        aliases: &["Indonesia", "Kemenag"],
+        region: "Indonesia",
+        authority: "Kementerian Agama Republik Indonesia",
+        parameters: KEMENTERIAN_AGAMA_INDONESIA,
+    },
+    BuiltinMethod {
+        id: "DIYANET_ISLERI_BASKANLIGI",
+        name: "Diyanet İşleri Başkanlığı",
+        aliases: &["Turkey", "Diyanet"],
+        region: "Turkey",
+        authority: "Diyanet İşleri Başkanlığı",
+        parameters: DIYANET_ISLERI_BASKANLIGI,
+    },
+    BuiltinMethod {
+        id: "UNION_DES_ORGANISATIONS_ISLAMIQUES_DE_FRANCE",
+        name: "Union des Organisations Islamiques de France",
+        aliases: &["France", "UOIF"],
+        region: "France",
+        authority: "Union des Organisations Islamiques de France",
+        parameters: UNION_DES_ORGANISATIONS_ISLAMIQUES_DE_FRANCE,
+    },
+    BuiltinMethod {
+        id: "SPIRITUAL_ADMINISTRATION_OF_MUSLIMS_OF_RUSSIA",
+        name: "Spiritual Administration of Muslims of Russia",
+        aliases: &["Russia"],
+        region: "Russia",
+        authority: "Spiritual Administration of Muslims of Russia",
+        parameters: SPIRITUAL_ADMINISTRATION_OF_MUSLIMS_OF_RUSSIA,
+    },
+    BuiltinMethod {
+        id: "MINISTRY_OF_HABOUS_AND_ISLAMIC_AFFAIRS_MOROCCO",
+        name: "Ministry of Habous and Islamic Affairs, Morocco",
+        aliases: &["Morocco"],
+        region: "Morocco",
+        authority: "Ministry of Habous and Islamic Affairs",
+        parameters: MINISTRY_OF_HABOUS_AND_ISLAMIC_AFFAIRS_MOROCCO,
+    },
+    BuiltinMethod {
+        id: "MINISTRY_OF_RELIGIOUS_AFFAIRS_ALGERIA",
+        name: "Ministry of Religious Affairs, Algeria",
+        aliases: &["Algeria"],
+        region: "Algeria",
+        authority: "Ministry of Religious Affairs and Wakfs",
+        parameters: MINISTRY_OF_RELIGIOUS_AFFAIRS_ALGERIA,
+    },
+];
+
+impl From<&BuiltinMethod> for Method {
+    fn from(method: &BuiltinMethod) -> Self {
+        Self {
+            id: method.id.into(),
+            name: method.name.into(),
+            aliases: method
+                .aliases
+                .iter()
+                .map(|alias| alias.to_string())
+                .collect(),
+            region: method.region.into(),
+            authority: method.authority.into(),
+            parameters: method.parameters.clone(),
         }
-        "INSTITUTE_OF_GEOPHYSICS_UNIVERSITY_OF_TEHRAN" | "Tehran" => {
-            Some(INSTITUTE_OF_GEOPHYSICS_UNIVERSITY_OF_TEHRAN)
+    }
+}
+
+/// Named calculation methods, the built-in ones and those registered at runtime
+///
+/// ```rust
+/// use praytimes::{methods::{self, Method, MethodRegistry}, types::Parameters};
+///
+/// let mut registry = MethodRegistry::default();
+/// registry.register(Method {
+///     id: "MY_MOSQUE".into(),
+///     name: "My Mosque".into(),
+///     aliases: vec!["Mosque".into()],
+///     region: "Neighborhood".into(),
+///     authority: "The imam".into(),
+///     parameters: Parameters {
+///         asr: praytimes::types::AsrFactor { factor: 2.0 },
+///         ..methods::MUSLIM_WORLD_LEAGUE
+///     },
+/// });
+///
+/// assert_eq!(registry.get("mosque").unwrap().id, "MY_MOSQUE");
+/// let karachi = registry.find_by_parameters(&methods::UNIVERSITY_OF_ISLAMIC_SCIENCES_KARACHI);
+/// assert_eq!(karachi.unwrap().id, "UNIVERSITY_OF_ISLAMIC_SCIENCES_KARACHI");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodRegistry {
+    methods: Vec<Method>,
+}
+
+impl Default for MethodRegistry {
+    /// registry of the built-in methods
+    fn default() -> Self {
+        Self {
+            methods: BUILTIN_METHODS.iter().map(Method::from).collect(),
         }
-        "SHIA_ITHNA_ASHARI_LEVA_INSTITUTE_QUM" | "Jafari" => {
-            Some(SHIA_ITHNA_ASHARI_LEVA_INSTITUTE_QUM)
+    }
+}
+
+impl MethodRegistry {
+    /// An empty registry, without the built-in methods
+    pub fn empty() -> Self {
+        Self { methods: vec![] }
+    }
+
+    /// Add a method, replacing ( and returning ) the method with the same id
+    pub fn register(&mut self, method: Method) -> Option<Method> {
+        match self.methods.iter_mut().find(|known| known.id == method.id) {
+            Some(known) => Some(core::mem::replace(known, method)),
+            None => {
+                self.methods.push(method);
+                None
+            }
         }
-        _ => None,
     }
+
+    /// Method by its id or one of its aliases, ignoring the case
+    ///
+    /// ids are matched before aliases, and the methods registered later win on a shared alias
+    pub fn get(&self, name: &str) -> Option<&Method> {
+        self.methods
+            .iter()
+            .find(|method| method.id.eq_ignore_ascii_case(name))
+            .or_else(|| {
+                self.methods.iter().rev().find(|method| {
+                    method
+                        .aliases
+                        .iter()
+                        .any(|alias| alias.eq_ignore_ascii_case(name))
+                })
+            })
+    }
+
+    /// The known method with exactly these parameters, if any
+    pub fn find_by_parameters(&self, parameters: &Parameters) -> Option<&Method> {
+        self.methods
+            .iter()
+            .find(|method| method.parameters == *parameters)
+    }
+
+    /// All the methods, in registration order
+    pub fn iter(&self) -> impl Iterator<Item = &Method> {
+        self.methods.iter()
+    }
+}
+
+/// Parameters of a built-in method by its id or alias ( see [`MethodRegistry::get`] )
+pub fn get_method_by_name(name: &str) -> Option<Parameters> {
+    MethodRegistry::default()
+        .get(name)
+        .map(|method| method.parameters.clone())
 }
@@ -0,0 +1,158 @@
+//! Qibla direction and distance to the Kaaba
+//!
+//! besides the direction, twice a year the sun passes exactly over the Kaaba at noon in Makkah ( and twice over its
+//! antipode ), at that instant the shadows of vertical objects everywhere the sun is up point away from ( or toward ) the Qibla.
+//!
+//! ```rust
+//! use praytimes::{qibla::{self, Qibla}, solar::Almanac, types::Location};
+//!
+//! let london = Location {
+//!     latitude: 51.5074,
+//!     longitude: -0.1278,
+//!     ..Default::default()
+//! };
+//! let qibla = Qibla::new(&london);
+//! assert!((qibla.direction - 118.99).abs() < 0.01);
+//!
+//! let transits = qibla::sun_over_kaaba(2024, &Almanac);
+//! assert_eq!(transits.len(), 4);
+//! ```
+use alloc::vec::Vec;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::solar::SolarModel;
+use crate::types::Location;
+use crate::utils::{d_math, julian_date::to_julian_date, numbers::fix_angle, numbers::fix_hour};
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+use crate::utils::float::Float;
+
+/// location of the Kaaba
+pub const KAABA: Location = Location {
+    latitude: 21.4225,
+    longitude: 39.8262,
+    elevation: 0.0,
+    atmosphere: None,
+};
+
+/// mean radius of the earth in kilometers
+const EARTH_RADIUS: f64 = 6371.0088;
+
+/// direction and distance to the Kaaba from a location
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Qibla {
+    /// initial bearing of the great circle to the Kaaba in degrees, clockwise from the true north
+    pub direction: f64,
+
+    /// great circle distance to the Kaaba in kilometers
+    pub distance: f64,
+}
+
+impl Qibla {
+    /// Qibla of a location
+    pub fn new(location: &Location) -> Self {
+        let delta_longitude = KAABA.longitude - location.longitude;
+        let direction = d_math::arctan2(
+            d_math::sin(delta_longitude),
+            d_math::cos(location.latitude) * d_math::tan(KAABA.latitude)
+                - d_math::sin(location.latitude) * d_math::cos(delta_longitude),
+        );
+
+        let haversine = d_math::sin((KAABA.latitude - location.latitude) / 2.0).powi(2)
+            + d_math::cos(location.latitude)
+                * d_math::cos(KAABA.latitude)
+                * d_math::sin(delta_longitude / 2.0).powi(2);
+        let distance = 2.0 * EARTH_RADIUS * haversine.sqrt().asin();
+
+        Self {
+            direction: fix_angle(direction),
+            distance,
+        }
+    }
+}
+
+/// an instant when the sun is at the zenith of the Kaaba or of its antipode
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KaabaTransit {
+    /// the utc instant, noon in Makkah ( or midnight for the antipode )
+    pub instant: NaiveDateTime,
+
+    /// whether the sun is over the antipode of the Kaaba, shadows point toward the Qibla instead of away from it
+    pub antipode: bool,
+}
+
+/// The instants in a year when the sun is over the Kaaba or its antipode, sorted
+///
+/// those are the solar noons of the Kaaba ( or its antipode ) when the declination of the sun is the
+/// closest to their latitude, usually around 27 May, 15 July, 28 November and 13 January
+pub fn sun_over_kaaba(year: i32, solar_model: &dyn SolarModel) -> Vec<KaabaTransit> {
+    let antipode = Location {
+        latitude: -KAABA.latitude,
+        longitude: KAABA.longitude - 180.0,
+        ..KAABA
+    };
+
+    let mut transits: Vec<KaabaTransit> = [(&KAABA, false), (&antipode, true)]
+        .into_iter()
+        .flat_map(|(location, is_antipode)| {
+            zenith_passages(year, location, solar_model)
+                .into_iter()
+                .map(move |instant| KaabaTransit {
+                    instant,
+                    antipode: is_antipode,
+                })
+        })
+        .collect();
+    transits.sort_by_key(|transit| transit.instant);
+    transits
+}
+
+/// solar noons of the days when the sun passes the closest to the zenith of a location
+fn zenith_passages(
+    year: i32,
+    location: &Location,
+    solar_model: &dyn SolarModel,
+) -> Vec<NaiveDateTime> {
+    // distance of the sun from the zenith at the noon of a day, signed by the side of the zenith
+    let noon = |date: &NaiveDate| {
+        let julian_date = to_julian_date(date, location);
+        let equation = solar_model.sun_position(julian_date + 0.5).equation;
+        let hours = fix_hour(12.0 - equation) - location.longitude / 15.0;
+        let declination = solar_model
+            .sun_position(julian_date + location.longitude / 360.0 + hours / 24.0)
+            .declination;
+        (hours, declination - location.latitude)
+    };
+
+    let days: Vec<(NaiveDate, f64, f64)> = NaiveDate::from_ymd_opt(year, 1, 1)
+        .into_iter()
+        .flat_map(|first| first.iter_days())
+        .take_while(|date| date.year() == year)
+        .map(|date| {
+            let (hours, offset) = noon(&date);
+            (date, hours, offset)
+        })
+        .collect();
+
+    days.windows(2)
+        .filter(|pair| pair[0].2.signum() != pair[1].2.signum())
+        .map(|pair| {
+            if pair[0].2.abs() < pair[1].2.abs() {
+                pair[0]
+            } else {
+                pair[1]
+            }
+        })
+        .filter_map(|(date, hours, _)| {
+            let milliseconds = (hours * 3600.0 * 1000.0) as i64;
+            NaiveDateTime::from_timestamp_millis(
+                date.and_hms_opt(0, 0, 0)?.timestamp_millis() + milliseconds,
+            )
+        })
+        .collect()
+}
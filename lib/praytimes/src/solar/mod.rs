@@ -0,0 +1,186 @@
+//! Solar ephemeris models used for the calculation
+//!
+//! the calculator only needs the sun's declination and the equation of time, a model provides both
+//! for any julian date. [`Almanac`] is the default, [`Vsop87`] is slower but more precise. the calculator computes
+//! the positions of the model at 0h of each day and interpolates between them, which moves the times by a few
+//! milliseconds.
+//!
+//! ```rust
+//! use praytimes::{methods, solar::Vsop87, Calculator};
+//!
+//! let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default())
+//!     .with_solar_model(Vsop87);
+//! ```
+//!
+//! the position of the sun in the sky of a location is available with [`coordinates`] ( or [`crate::Calculator::sun_coordinates`] )
+use alloc::collections::VecDeque;
+use core::{cell::RefCell, fmt::Debug};
+
+use chrono::NaiveDateTime;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+use crate::utils::float::Float;
+use crate::types::Location;
+use crate::utils::{d_math::horizontal, julian_date::julian_from_datetime};
+
+mod almanac;
+mod vsop87;
+
+pub use almanac::Almanac;
+pub use vsop87::Vsop87;
+
+/// position of the sun needed for the calculation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SunPosition {
+    /// apparent declination in degrees
+    pub declination: f64,
+    /// equation of time in hours ( apparent minus mean solar time )
+    pub equation: f64,
+}
+
+/// a solar ephemeris
+pub trait SolarModel: Debug + Send + Sync {
+    /// position of the sun at a julian date ( universal time )
+    fn sun_position(&self, jd: f64) -> SunPosition;
+}
+
+/// position of the sun in the sky of a location at an instant, angles are in degrees
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SunCoordinates {
+    /// apparent altitude of the sun's center above the horizon, corrected for the refraction
+    pub altitude: f64,
+    /// azimuth clockwise from the true north
+    pub azimuth: f64,
+    /// local hour angle between -180 and 180, negative before the noon
+    pub hour_angle: f64,
+    /// apparent declination
+    pub declination: f64,
+    /// equation of time in hours
+    pub equation: f64,
+}
+
+/// Position of the sun in the sky of a location at a utc instant
+///
+/// the refraction uses the atmosphere of the location, or the standard one when it's absent
+pub fn coordinates(
+    solar_model: &dyn SolarModel,
+    location: &Location,
+    time: &NaiveDateTime,
+) -> SunCoordinates {
+    let jd = julian_from_datetime(time);
+    let position = solar_model.sun_position(jd);
+    let hour_angle = hour_angle(location, jd, &position);
+    let (altitude, azimuth) = horizontal(hour_angle, position.declination, location.latitude);
+
+    SunCoordinates {
+        altitude: location
+            .atmosphere
+            .unwrap_or_default()
+            .apparent_altitude(altitude),
+        azimuth,
+        hour_angle,
+        declination: position.declination,
+        equation: position.equation,
+    }
+}
+
+/// geocentric altitude and azimuth of the sun's center at a julian date, without refraction
+pub(crate) fn true_horizontal(
+    solar_model: &dyn SolarModel,
+    location: &Location,
+    jd: f64,
+) -> (f64, f64) {
+    let position = solar_model.sun_position(jd);
+    horizontal(
+        hour_angle(location, jd, &position),
+        position.declination,
+        location.latitude,
+    )
+}
+
+/// local hour angle of the sun in degrees between -180 and 180
+fn hour_angle(location: &Location, jd: f64, position: &SunPosition) -> f64 {
+    let hours = (jd + 0.5).rem_euclid(1.0) * 24.0;
+    let angle = 15.0 * (hours + position.equation - 12.0) + location.longitude;
+    180.0 - (180.0 - angle).rem_euclid(360.0)
+}
+
+/// positions of a solar model interpolated between its positions at 0h of consecutive days, computed once for all
+/// the calculations sharing them instead of at every time of every day
+///
+/// the cubic interpolation moves the times by a few milliseconds from the positions of the model, the same for a day
+/// whatever the days calculated before
+#[derive(Debug)]
+pub(crate) struct DailyPositions<'a> {
+    solar_model: &'a dyn SolarModel,
+    /// the first day and the positions of the model at the julian dates `day + 0.5` of the following days, `None`
+    /// when not computed yet
+    positions: RefCell<(i64, VecDeque<Option<SunPosition>>)>,
+}
+
+impl<'a> DailyPositions<'a> {
+    pub(crate) fn new(solar_model: &'a dyn SolarModel) -> Self {
+        DailyPositions {
+            solar_model,
+            positions: RefCell::new((0, VecDeque::new())),
+        }
+    }
+
+    pub(crate) fn solar_model(&self) -> &'a dyn SolarModel {
+        self.solar_model
+    }
+
+    /// position of the sun at a julian date ( universal time ), from the positions of the two days before and after
+    pub(crate) fn sun_position(&self, jd: f64) -> SunPosition {
+        if !jd.is_finite() {
+            return self.solar_model.sun_position(jd);
+        }
+        let x = jd - 0.5;
+        let day = x.floor();
+        let p = x - day;
+        let weights = [
+            -p * (p - 1.0) * (p - 2.0) / 6.0,
+            (p + 1.0) * (p - 1.0) * (p - 2.0) / 2.0,
+            -(p + 1.0) * p * (p - 2.0) / 2.0,
+            (p + 1.0) * p * (p - 1.0) / 6.0,
+        ];
+
+        let (first, last) = (day as i64 - 1, day as i64 + 2);
+        let (start, positions) = &mut *self.positions.borrow_mut();
+        if positions.is_empty() {
+            *start = first;
+        }
+        while *start > first {
+            positions.push_front(None);
+            *start -= 1;
+        }
+        while *start + (positions.len() as i64) <= last {
+            positions.push_back(None);
+        }
+        let around: [SunPosition; 4] = core::array::from_fn(|i| {
+            let day = first + i as i64;
+            *positions[(day - *start) as usize]
+                .get_or_insert_with(|| self.solar_model.sun_position(day as f64 + 0.5))
+        });
+
+        // the equation of time of a model can wrap by 24 hours from a day to the next
+        let reference = around[1].equation;
+        around.iter().zip(weights).fold(
+            SunPosition {
+                declination: 0.0,
+                equation: 0.0,
+            },
+            |sum, (position, weight)| SunPosition {
+                declination: sum.declination + weight * position.declination,
+                equation: sum.equation
+                    + weight
+                        * (position.equation
+                            - 24.0 * ((position.equation - reference) / 24.0).round()),
+            },
+        )
+    }
+}
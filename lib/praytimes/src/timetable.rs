@@ -0,0 +1,85 @@
+//! Prayer times of a range of days
+//!
+//! a timetable is calculated with [`crate::Calculator::timetable`], or with [`crate::Calculator::timetables`] for
+//! many locations ( in parallel with the `rayon` feature ).
+//!
+//! ```rust
+//! use praytimes::{methods, timetable::DateRange, types::Location, Calculator};
+//!
+//! let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+//! let location = Location { latitude: 35.7, longitude: 51.4, ..Default::default() };
+//!
+//! let timetable = calculator.timetable(&location, &DateRange::month(2024, 2).unwrap());
+//! assert_eq!(timetable.rows.len(), 29);
+//! ```
+use alloc::vec::Vec;
+
+use chrono::{Datelike, NaiveDate};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Location, PraytimesOutput};
+
+/// days from `start` to `end`, both included
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Self {
+        Self { start, end }
+    }
+
+    /// the days of a month, `None` for an invalid month
+    pub fn month(year: i32, month: u32) -> Option<Self> {
+        let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let next = match month {
+            12 => NaiveDate::from_ymd_opt(year + 1, 1, 1)?,
+            _ => NaiveDate::from_ymd_opt(year, month + 1, 1)?,
+        };
+        Some(Self::new(start, next.pred_opt()?))
+    }
+
+    /// the days of a year, `None` out of the range of chrono
+    pub fn year(year: i32) -> Option<Self> {
+        Some(Self::new(
+            NaiveDate::from_ymd_opt(year, 1, 1)?,
+            NaiveDate::from_ymd_opt(year, 12, 31)?,
+        ))
+    }
+
+    /// the days in order, nothing when `end` is before `start`
+    pub fn days(&self) -> impl Iterator<Item = NaiveDate> {
+        let end = self.end;
+        self.start.iter_days().take_while(move |date| *date <= end)
+    }
+
+    /// number of days
+    pub fn len(&self) -> usize {
+        (self.end.num_days_from_ce() - self.start.num_days_from_ce() + 1).max(0) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// prayer times of a day of a timetable
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimetableRow {
+    pub date: NaiveDate,
+    pub times: PraytimesOutput,
+}
+
+/// prayer times of a location for a range of days, a row per day in order
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timetable {
+    pub location: Location,
+    pub rows: Vec<TimetableRow>,
+}
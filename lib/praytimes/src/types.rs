@@ -1,7 +1,13 @@
+use alloc::{borrow::Cow, format, string::String, vec, vec::Vec};
 use core::fmt;
-use std::fmt::Display;
+use core::fmt::Display;
 
-use chrono::{NaiveDateTime, TimeZone};
+use chrono::{FixedOffset, NaiveDateTime, Offset, TimeZone, Utc};
+
+use crate::error::CalcError;
+use crate::hijri::HijriDate;
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+use crate::utils::float::Float;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -27,6 +33,13 @@ pub enum HighLatsMethod {
     OneSeventh,
     /// The angle-based method (recommended)
     AngleBased,
+    /// The seasonal method of the Moonsighting Committee ( Khalid Shaukat ), fajr and isha are at most a number of
+    /// minutes from sunrise and sunset that depends on the latitude and the season, and 1/7th of the night above 55°
+    MoonsightingCommittee,
+    /// The portion of the night taken by the twilight at 48.5° of latitude
+    NearestLatitude,
+    /// The portion of the night between the event and sunrise ( or sunset ) on the nearest day when the event exists
+    NearestDay,
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -59,10 +72,49 @@ pub struct AsrFactor {
     pub factor: f64,
 }
 
+/// side of the noon of a shadow based time ( see [`crate::Calculator::shadow_time`] )
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum NoonSide {
+    /// in the morning, while the shadow shortens
+    BeforeNoon,
+    /// in the afternoon, while the shadow lengthens ( like asr )
+    AfterNoon,
+}
+
+/// twilight of the evening used by the seasonal method of the Moonsighting Committee
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum Shafaq {
+    /// a combination of ahmar and abyad, less difficult in high latitudes ( default )
+    #[default]
+    General,
+    /// the redness in the sky, the earliest isha
+    Ahmar,
+    /// the whiteness in the sky, the latest isha
+    Abyad,
+}
+
+/// sun angle below the horizon limited by the seasonal time of the Moonsighting Committee ( Khalid Shaukat )
+///
+/// the time from the event to sunrise ( or sunset ) is at most a number of minutes that depends on the latitude and
+/// the day of the year, above 55° of latitude the high latitudes method is used instead
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Seasonal {
+    pub degree: f64,
+    /// twilight of the evening events ( isha ), ignored in the morning
+    pub shafaq: Shafaq,
+}
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(untagged))]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CalculationUnit {
+    // declared before degrees, untagged deserialization picks the first matching variant
+    #[cfg_attr(feature = "serde", serde(rename = "seasonal"))]
+    Seasonal(Seasonal),
+
     #[cfg_attr(feature = "serde", serde(rename = "degree"))]
     Degrees(Degrees),
 
@@ -70,6 +122,49 @@ pub enum CalculationUnit {
     Minutes(Minutes),
 }
 
+/// calculation unit of an event replaced during a month of the hijri calendar, like isha in ramadan
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HijriRule {
+    /// month of the hijri calendar, 1 (Muharram) to 12 (Dhu al-Hijjah)
+    pub month: u32,
+    /// imsak, fajr, maghrib, isha or dhuhr ( with minutes only )
+    pub event: PraytimeType,
+    pub unit: CalculationUnit,
+}
+
+/// precaution minutes of a method ( ihtiyat, or temkin in turkey ) added to the calculated times, the user's
+/// [`TuneOffsets`] are applied on top of them
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Ihtiyat {
+    pub imsak: f64,
+    pub fajr: f64,
+    pub sunrise: f64,
+    pub dhuhr: f64,
+    pub asr: f64,
+    pub sunset: f64,
+    pub maghrib: f64,
+    pub isha: f64,
+    pub midnight: f64,
+}
+
+impl Ihtiyat {
+    /// no precaution
+    pub const NONE: Ihtiyat = Ihtiyat {
+        imsak: 0.0,
+        fajr: 0.0,
+        sunrise: 0.0,
+        dhuhr: 0.0,
+        asr: 0.0,
+        sunset: 0.0,
+        maghrib: 0.0,
+        isha: 0.0,
+        midnight: 0.0,
+    };
+}
+
 /// Parameters for the calculation
 /// normally you wouldn't specify these manually and use a method instead (see [`crate::methods`])
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -78,8 +173,8 @@ pub struct Parameters {
     /// sun angle below the horizon or minutes before fajr
     pub imsak: CalculationUnit,
 
-    /// sun angle below the horizon to calculate fajr
-    pub fajr: Degrees,
+    /// sun angle below the horizon ( fixed or seasonal ) or minutes before sunrise
+    pub fajr: CalculationUnit,
 
     /// minutes after (or before with negative numbers) the midday
     pub dhuhr: Minutes,
@@ -99,6 +194,136 @@ pub struct Parameters {
     /// adjustment method for higher latitudes (see [`HighLatsMethod`])
     #[cfg_attr(feature = "serde", serde(rename = "highLats"))]
     pub high_latitudes: HighLatsMethod,
+
+    /// events calculated differently in some hijri months, the day is converted with the hijri calendar of the
+    /// calculator ( Umm al-Qura by default )
+    #[cfg_attr(feature = "serde", serde(default, rename = "hijriRules"))]
+    pub hijri_rules: Cow<'static, [HijriRule]>,
+
+    /// minutes added to the calculated times by the method
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ihtiyat: Ihtiyat,
+
+    /// rounding of the times published by the method, applied after the ihtiyat and the tuning offsets
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rounding: RoundingPolicy,
+}
+
+impl Parameters {
+    /// Check the parameters can be used for the calculation
+    pub fn validate(&self) -> Result<(), CalcError> {
+        use PraytimeType::*;
+        validate_unit(Imsak, &self.imsak)?;
+        validate_unit(Fajr, &self.fajr)?;
+        validate_unit(Dhuhr, &CalculationUnit::Minutes(self.dhuhr))?;
+        validate_unit(Maghrib, &self.maghrib)?;
+        validate_unit(Isha, &self.isha)?;
+
+        let factor = self.asr.factor;
+        if !(factor.is_finite() && factor > 0.0) {
+            return Err(CalcError::InvalidAsrFactor(factor));
+        }
+
+        let ihtiyat = [
+            (Imsak, self.ihtiyat.imsak),
+            (Fajr, self.ihtiyat.fajr),
+            (Sunrise, self.ihtiyat.sunrise),
+            (Dhuhr, self.ihtiyat.dhuhr),
+            (Asr, self.ihtiyat.asr),
+            (Sunset, self.ihtiyat.sunset),
+            (Maghrib, self.ihtiyat.maghrib),
+            (Isha, self.ihtiyat.isha),
+            (Midnight, self.ihtiyat.midnight),
+        ];
+        for (event, minutes) in ihtiyat {
+            validate_unit(event, &CalculationUnit::Minutes(Minutes { minutes }))?;
+        }
+
+        for rule in self.hijri_rules.iter() {
+            let supported = matches!(
+                (rule.event, rule.unit),
+                (Imsak | Fajr | Maghrib | Isha, _) | (Dhuhr, CalculationUnit::Minutes(_))
+            );
+            if !(1..=12).contains(&rule.month) || !supported {
+                return Err(CalcError::InvalidHijriRule {
+                    month: rule.month,
+                    event: rule.event,
+                });
+            }
+            validate_unit(rule.event, &rule.unit)?;
+        }
+        Ok(())
+    }
+
+    /// The parameters of a day in a hijri month, after applying its [`HijriRule`]s
+    pub fn for_hijri_month(&self, month: u32) -> Parameters {
+        let mut params = Parameters {
+            hijri_rules: Cow::Borrowed(&[]),
+            ..self.clone()
+        };
+        for rule in self.hijri_rules.iter().filter(|rule| rule.month == month) {
+            match (rule.event, rule.unit) {
+                (PraytimeType::Imsak, unit) => params.imsak = unit,
+                (PraytimeType::Fajr, unit) => params.fajr = unit,
+                (PraytimeType::Dhuhr, CalculationUnit::Minutes(minutes)) => params.dhuhr = minutes,
+                (PraytimeType::Maghrib, unit) => params.maghrib = unit,
+                (PraytimeType::Isha, unit) => params.isha = unit,
+                _ => {}
+            }
+        }
+        params
+    }
+
+    /// Use a shafaq for the seasonal isha ( see [`Seasonal`] ), other isha rules are unchanged
+    pub fn with_shafaq(self, shafaq: Shafaq) -> Self {
+        let isha = match self.isha {
+            CalculationUnit::Seasonal(Seasonal { degree, .. }) => {
+                CalculationUnit::Seasonal(Seasonal { degree, shafaq })
+            }
+            isha => isha,
+        };
+        Self { isha, ..self }
+    }
+
+    /// Validated parameters, useful when they are not one of the [`crate::methods`]
+    pub fn validated(self) -> Result<Self, CalcError> {
+        self.validate().map(|_| self)
+    }
+}
+
+fn validate_unit(event: PraytimeType, unit: &CalculationUnit) -> Result<(), CalcError> {
+    match *unit {
+        CalculationUnit::Degrees(Degrees { degree })
+        | CalculationUnit::Seasonal(Seasonal { degree, .. })
+            if !(0.0..90.0).contains(&degree) =>
+        {
+            Err(CalcError::InvalidAngle { event, degree })
+        }
+        CalculationUnit::Minutes(Minutes { minutes }) if !minutes.is_finite() => {
+            Err(CalcError::InvalidMinutes { event, minutes })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// How many times the events are recomputed, each time with the sun position at the previous result
+///
+/// the sun position of the first computation is taken at fixed guesses ( 5:00 for fajr, 18:00 for sunset, ... in local mean time )
+/// like PrayTimes.js, refining makes the events accurate to the second near the solstices and in high latitudes
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Refinement {
+    /// a fixed number of refinements after the first computation, `0` is the PrayTimes.js behavior
+    Iterations(u32),
+
+    /// refine until the events move less than `tolerance` seconds, at most `max_iterations` times
+    Converge { tolerance: f64, max_iterations: u32 },
+}
+
+impl Default for Refinement {
+    fn default() -> Self {
+        Refinement::Iterations(0)
+    }
 }
 
 /// Specifies the geographic coordinates and elevation of a location.
@@ -117,8 +342,133 @@ pub struct Location {
     /// Should be a positive number.
     #[cfg_attr(feature = "serde", serde(default))]
     pub elevation: f64,
+
+    /// Weather conditions used for the atmospheric refraction.
+    /// when absent the standard refraction of 0.833° for sunrise and sunset is used.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub atmosphere: Option<Atmosphere>,
+}
+
+impl Location {
+    /// Create a validated location without atmosphere ( see [`Location::validate`] )
+    pub fn new(latitude: f64, longitude: f64, elevation: f64) -> Result<Self, CalcError> {
+        Self {
+            latitude,
+            longitude,
+            elevation,
+            atmosphere: None,
+        }
+        .validated()
+    }
+
+    /// Check the location can be used for the calculation
+    pub fn validate(&self) -> Result<(), CalcError> {
+        if !(-90.0..=90.0).contains(&self.latitude) {
+            return Err(CalcError::InvalidLatitude(self.latitude));
+        }
+        if !(-180.0..=180.0).contains(&self.longitude) {
+            return Err(CalcError::InvalidLongitude(self.longitude));
+        }
+        if !(self.elevation.is_finite() && self.elevation >= 0.0) {
+            return Err(CalcError::InvalidElevation(self.elevation));
+        }
+        if let Some(Atmosphere {
+            temperature,
+            pressure,
+        }) = self.atmosphere
+        {
+            if !(temperature.is_finite() && temperature > -273.15) {
+                return Err(CalcError::InvalidTemperature(temperature));
+            }
+            if !(pressure.is_finite() && pressure > 0.0) {
+                return Err(CalcError::InvalidPressure(pressure));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validated location
+    pub fn validated(self) -> Result<Self, CalcError> {
+        self.validate().map(|_| self)
+    }
+}
+
+/// Temperature and pressure at a location, they change how much the atmosphere bends the sunlight near the horizon.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Atmosphere {
+    /// The air temperature in degrees Celsius.
+    #[cfg_attr(feature = "serde", serde(default = "Atmosphere::default_temperature"))]
+    pub temperature: f64,
+
+    /// The air pressure in millibars ( hPa ).
+    #[cfg_attr(feature = "serde", serde(default = "Atmosphere::default_pressure"))]
+    pub pressure: f64,
+}
+
+impl Atmosphere {
+    fn default_temperature() -> f64 {
+        10.0
+    }
+
+    fn default_pressure() -> f64 {
+        1010.0
+    }
+
+    /// refraction in degrees of the sun seen at an apparent altitude ( Bennett's formula )
+    ///
+    /// like the NREL Solar Position Algorithm there is no refraction once the sun is completely below the horizon, so
+    /// the atmosphere doesn't change the angles of the twilight events ( fajr, isha, ... )
+    pub fn refraction(&self, altitude: f64) -> f64 {
+        if altitude < -(SUN_SEMI_DIAMETER + STANDARD_REFRACTION) {
+            return 0.0;
+        }
+        Self::bennett(altitude) * self.scale()
+    }
+
+    /// apparent altitude in degrees of the sun seen at a true ( airless ) altitude ( Sæmundsson's formula )
+    ///
+    /// the inverse of [`Atmosphere::refraction`], there is no refraction once the sun is completely below the horizon
+    pub fn apparent_altitude(&self, altitude: f64) -> f64 {
+        let minutes = 1.02 / (altitude + 10.3 / (altitude + 5.11)).to_radians().tan();
+        let apparent = altitude + minutes / 60.0 * self.scale();
+
+        if apparent < -(SUN_SEMI_DIAMETER + STANDARD_REFRACTION) {
+            altitude
+        } else {
+            apparent
+        }
+    }
+
+    /// refraction in degrees of Bennett's formula in its own standard atmosphere
+    fn bennett(altitude: f64) -> f64 {
+        1.0 / (altitude + 7.31 / (altitude + 4.4)).to_radians().tan() / 60.0
+    }
+
+    /// refraction relative to the standard atmosphere, where the refraction at the horizon is the conventional
+    /// `STANDARD_REFRACTION` ( 0.833° with the semi-diameter of the sun, instead of 0.842° for Bennett's formula )
+    fn scale(&self) -> f64 {
+        let horizon = STANDARD_REFRACTION / Self::bennett(0.0);
+        horizon * (self.pressure / 1010.0) * (283.0 / (273.0 + self.temperature))
+    }
 }
 
+impl Default for Atmosphere {
+    /// standard atmosphere ( 10°C and 1010 millibars )
+    fn default() -> Self {
+        Self {
+            temperature: Self::default_temperature(),
+            pressure: Self::default_pressure(),
+        }
+    }
+}
+
+/// apparent radius of the sun in degrees
+pub(crate) const SUN_SEMI_DIAMETER: f64 = 0.2667;
+
+/// refraction at the horizon in the standard atmosphere in degrees, the sun rises and sets 0.833° below the horizon
+pub(crate) const STANDARD_REFRACTION: f64 = 0.833 - SUN_SEMI_DIAMETER;
+
 /// calculated prayertimes
 ///
 /// and if doesn't exists ( for example in high latitudes ) None
@@ -163,6 +513,34 @@ pub struct PraytimesOutput {
     /// Midnight
     /// calculation based on sunset to sunrise ( or fajr in some methods ).
     pub midnight: Option<NaiveDateTime>,
+
+    /// The calculated day in the hijri calendar.
+    /// only available when the calculator has a hijri calendar ( see [`crate::Calculator::with_hijri_calendar`] ).
+    pub hijri: Option<HijriDate>,
+
+    /// Events whose time was adjusted for the high latitudes ( see [`HighLatsMethod`] ).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub adjusted: Vec<PraytimeType>,
+}
+
+/// Prayer times of a day in fractional utc hours since 0h of the day, see [`crate::Calculator::calculate_hours`]
+///
+/// the hours can be negative or past 24 when an event falls on the utc day before or after
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PraytimesHours {
+    pub imsak: Option<f64>,
+    pub fajr: Option<f64>,
+    pub sunrise: Option<f64>,
+    pub dhuhr: Option<f64>,
+    pub asr: Option<f64>,
+    pub sunset: Option<f64>,
+    pub maghrib: Option<f64>,
+    pub isha: Option<f64>,
+    pub midnight: Option<f64>,
+    /// Events whose time was adjusted for the high latitudes ( see [`HighLatsMethod`] ).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub adjusted: Vec<PraytimeType>,
 }
 /// Formatted times
 /// you can easily use [`PraytimesOutput`]'s format_time method to format the PraytimesOutput into a
@@ -180,6 +558,8 @@ pub struct FormattedTimes {
     pub maghrib: Option<String>,
     pub isha: Option<String>,
     pub midnight: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub adjusted: Vec<PraytimeType>,
 }
 impl PraytimesOutput {
     /// format times with a given format ( see [chrono's format docs for more information ](https://docs.rs/chrono/latest/chrono/format/strftime/index.html) ) and in a given timezone
@@ -198,6 +578,7 @@ impl PraytimesOutput {
             maghrib: self.maghrib.map(|d| format_time(d, format, zone)),
             isha: self.isha.map(|d| format_time(d, format, zone)),
             midnight: self.midnight.map(|d| format_time(d, format, zone)),
+            adjusted: self.adjusted.clone(),
         }
     }
 
@@ -214,10 +595,9 @@ impl PraytimesOutput {
             (Isha, self.isha),
             (Midnight, self.midnight),
         ];
-        return a
-            .into_iter()
+        a.into_iter()
             .filter_map(|(t, p)| p.map(|p| (t, p)))
-            .collect();
+            .collect()
     }
 }
 
@@ -245,6 +625,96 @@ pub struct TuneOffsets {
     pub midnight: Option<f64>,
 }
 
+/// how a time is rounded, the minutes are counted from the midnight of utc or of a local offset ( see
+/// [`Rounding::round_in`] )
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum Rounding {
+    /// keep the seconds ( default )
+    #[default]
+    None,
+    /// to the nearest minute
+    Nearest,
+    /// up to the next minute, unless already on a minute
+    Ceil,
+    /// down to the minute
+    Floor,
+    /// to the nearest multiple of some minutes ( like 5 minutes )
+    Minutes(u32),
+    /// up to the next multiple of some minutes, unless already on one
+    #[cfg_attr(feature = "serde", serde(rename = "ceilMinutes"))]
+    CeilMinutes(u32),
+    /// down to a multiple of some minutes
+    #[cfg_attr(feature = "serde", serde(rename = "floorMinutes"))]
+    FloorMinutes(u32),
+}
+
+impl Rounding {
+    /// Round a utc time
+    pub fn round(&self, time: NaiveDateTime) -> NaiveDateTime {
+        self.round_in(time, Utc.fix())
+    }
+
+    /// Round a utc time on the minutes of a local offset, like 04:12 of UTC+5:45 to 04:15 with `Minutes(5)`
+    pub fn round_in(&self, time: NaiveDateTime, offset: FixedOffset) -> NaiveDateTime {
+        let minute = 60 * 1000;
+        let shift = offset.local_minus_utc() as i64 * 1000;
+        let millis = time.timestamp_millis() + shift;
+        let period = |minutes: u32| minutes as i64 * minute;
+        let (period, offset) = match *self {
+            Rounding::None
+            | Rounding::Minutes(0)
+            | Rounding::CeilMinutes(0)
+            | Rounding::FloorMinutes(0) => return time,
+            Rounding::Nearest => (minute, minute / 2),
+            Rounding::Ceil => (minute, minute - 1),
+            Rounding::Floor => (minute, 0),
+            Rounding::Minutes(minutes) => (period(minutes), period(minutes) / 2),
+            Rounding::CeilMinutes(minutes) => (period(minutes), period(minutes) - 1),
+            Rounding::FloorMinutes(minutes) => (period(minutes), 0),
+        };
+        let rounded = (millis + offset).div_euclid(period) * period - shift;
+        NaiveDateTime::from_timestamp_millis(rounded).unwrap_or(time)
+    }
+}
+
+/// rounding of each time, applied after the tuning offsets
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RoundingPolicy {
+    pub imsak: Rounding,
+    pub fajr: Rounding,
+    pub sunrise: Rounding,
+    pub dhuhr: Rounding,
+    pub asr: Rounding,
+    pub sunset: Rounding,
+    pub maghrib: Rounding,
+    pub isha: Rounding,
+    pub midnight: Rounding,
+}
+
+impl RoundingPolicy {
+    /// No rounding, the times keep their seconds
+    pub const NONE: RoundingPolicy = RoundingPolicy::all(Rounding::None);
+
+    /// The same rounding for all the times
+    pub const fn all(rounding: Rounding) -> Self {
+        Self {
+            imsak: rounding,
+            fajr: rounding,
+            sunrise: rounding,
+            dhuhr: rounding,
+            asr: rounding,
+            sunset: rounding,
+            maghrib: rounding,
+            isha: rounding,
+            midnight: rounding,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
@@ -1,3 +1,6 @@
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+use crate::utils::float::Float;
+
 pub fn sin(degrees: f64) -> f64 {
     degrees.to_radians().sin()
 }
@@ -29,3 +32,15 @@ pub fn arccot(x: f64) -> f64 {
 pub fn arctan2(y: f64, x: f64) -> f64 {
     y.atan2(x).to_degrees()
 }
+
+/// altitude and azimuth ( clockwise from the north ) of a body from its hour angle and declination
+pub fn horizontal(hour_angle: f64, declination: f64, latitude: f64) -> (f64, f64) {
+    let altitude = arcsin(
+        sin(latitude) * sin(declination) + cos(latitude) * cos(declination) * cos(hour_angle),
+    );
+    let azimuth = arctan2(
+        sin(hour_angle),
+        cos(hour_angle) * sin(latitude) - tan(declination) * cos(latitude),
+    );
+    (altitude, crate::utils::numbers::fix_angle(azimuth + 180.0))
+}
@@ -1,5 +1,7 @@
-use chrono::{Datelike, NaiveDate};
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
 
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+use crate::utils::float::Float;
 use crate::types::Location;
 
 pub fn to_julian_date(date: &NaiveDate, location: &Location) -> f64 {
@@ -16,13 +18,68 @@ fn julian_from_gregorian(mut year: i32, mut month: u32, day: u32) -> f64 {
     let a = year / 100;
     let b = 2 - a + a / 4;
 
-    let jd = (365.25 * (year + 4716) as f64).floor()
-        + (30.6001 * (month + 1) as f64).floor() as f64
+    (365.25 * (year + 4716) as f64).floor()
+        + (30.6001 * (month + 1) as f64).floor()
         + day as f64
         + b as f64
-        - 1524.5;
+        - 1524.5
+}
+
+/// julian date of a utc instant
+pub fn julian_from_datetime(datetime: &NaiveDateTime) -> f64 {
+    2440587.5 + datetime.timestamp_millis() as f64 / 86_400_000.0
+}
+
+/// utc instant of a julian date
+pub fn datetime_from_julian(jd: f64) -> Option<NaiveDateTime> {
+    NaiveDateTime::from_timestamp_millis(((jd - 2440587.5) * 86_400_000.0).round() as i64)
+}
+
+/// civil date containing a julian date
+pub fn date_from_julian(jd: f64) -> Option<NaiveDate> {
+    let day_number = (jd + 0.5).floor() as i64 - 1721425;
+    NaiveDate::from_num_days_from_ce_opt(day_number.try_into().ok()?)
+}
 
-    jd
+/// difference between terrestrial and universal time in seconds at a julian date
+///
+/// polynomial expressions of Espenak and Meeus, valid from 1900 to 2150
+pub fn delta_t(jd: f64) -> f64 {
+    let year = 2000.0 + (jd - 2451544.5) / 365.2425;
+    match year {
+        y if y < 1920.0 => {
+            let t = y - 1900.0;
+            -2.79 + 1.494119 * t - 0.0598939 * t.powi(2) + 0.0061966 * t.powi(3)
+                - 0.000197 * t.powi(4)
+        }
+        y if y < 1941.0 => {
+            let t = y - 1920.0;
+            21.20 + 0.84493 * t - 0.076100 * t.powi(2) + 0.0020936 * t.powi(3)
+        }
+        y if y < 1961.0 => {
+            let t = y - 1950.0;
+            29.07 + 0.407 * t - t.powi(2) / 233.0 + t.powi(3) / 2547.0
+        }
+        y if y < 1986.0 => {
+            let t = y - 1975.0;
+            45.45 + 1.067 * t - t.powi(2) / 260.0 - t.powi(3) / 718.0
+        }
+        y if y < 2005.0 => {
+            let t = y - 2000.0;
+            63.86 + 0.3345 * t - 0.060374 * t.powi(2)
+                + 0.0017275 * t.powi(3)
+                + 0.000651814 * t.powi(4)
+                + 0.00002373599 * t.powi(5)
+        }
+        y if y < 2050.0 => {
+            let t = y - 2000.0;
+            62.92 + 0.32217 * t + 0.005589 * t.powi(2)
+        }
+        y => {
+            let u = (y - 1820.0) / 100.0;
+            -20.0 + 32.0 * u * u - 0.5628 * (2150.0 - y).max(0.0)
+        }
+    }
 }
 
 // TODO: write tests
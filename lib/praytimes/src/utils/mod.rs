@@ -1,4 +1,6 @@
 pub mod d_math;
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+pub mod float;
 pub mod julian_date;
+pub mod moon;
 pub mod numbers;
-pub mod sun_position;
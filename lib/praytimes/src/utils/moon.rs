@@ -0,0 +1,217 @@
+//! Lunar ephemeris based on the truncated series of Meeus, "Astronomical Algorithms" (ch. 47 and 49)
+use super::d_math::*;
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+use crate::utils::float::Float;
+use crate::utils::numbers::fix_angle;
+
+/// mean length of a lunation in days
+pub const SYNODIC_MONTH: f64 = 29.530588861;
+
+/// julian date of the mean new moon of lunation 0 (2000-01-06)
+pub const LUNATION_EPOCH: f64 = 2451550.09766;
+
+pub struct MoonPosition {
+    /// geocentric right ascension in degrees
+    pub right_ascension: f64,
+    /// geocentric declination in degrees
+    pub declination: f64,
+    /// distance between the centers of the earth and the moon in kilometers
+    pub distance: f64,
+}
+
+impl MoonPosition {
+    /// equatorial horizontal parallax in degrees
+    pub fn parallax(&self) -> f64 {
+        arcsin(6378.14 / self.distance)
+    }
+
+    /// geocentric semi diameter in degrees
+    pub fn semi_diameter(&self) -> f64 {
+        arcsin(1737.4 / self.distance)
+    }
+}
+
+/// lunation number of the new moon closest to a julian date
+pub fn lunation_near(jd: f64) -> f64 {
+    ((jd - LUNATION_EPOCH) / SYNODIC_MONTH).round()
+}
+
+/// julian date of the new moon (conjunction) of lunation `k` (0 is the new moon of 2000-01-06)
+///
+/// accurate to about a minute, the planetary corrections are omitted
+pub fn new_moon(k: f64) -> f64 {
+    let t = k / 1236.85;
+
+    let jde = LUNATION_EPOCH + SYNODIC_MONTH * k + 0.00015437 * t * t - 0.000000150 * t * t * t
+        + 0.00000000073 * t * t * t * t;
+
+    let e = 1.0 - 0.002516 * t - 0.0000074 * t * t;
+    let m = fix_angle(2.5534 + 29.10535670 * k - 0.0000014 * t * t - 0.00000011 * t * t * t);
+    let mp = fix_angle(
+        201.5643 + 385.81693528 * k + 0.0107582 * t * t + 0.00001238 * t * t * t
+            - 0.000000058 * t * t * t * t,
+    );
+    let f = fix_angle(
+        160.7108 + 390.67050284 * k - 0.0016118 * t * t - 0.00000227 * t * t * t
+            + 0.000000011 * t * t * t * t,
+    );
+    let omega = fix_angle(124.7746 - 1.56375588 * k + 0.0020672 * t * t + 0.00000215 * t * t * t);
+
+    let correction = -0.40720 * sin(mp)
+        + 0.17241 * e * sin(m)
+        + 0.01608 * sin(2.0 * mp)
+        + 0.01039 * sin(2.0 * f)
+        + 0.00739 * e * sin(mp - m)
+        - 0.00514 * e * sin(mp + m)
+        + 0.00208 * e * e * sin(2.0 * m)
+        - 0.00111 * sin(mp - 2.0 * f)
+        - 0.00057 * sin(mp + 2.0 * f)
+        + 0.00056 * e * sin(2.0 * mp + m)
+        - 0.00042 * sin(3.0 * mp)
+        + 0.00042 * e * sin(m + 2.0 * f)
+        + 0.00038 * e * sin(m - 2.0 * f)
+        - 0.00024 * e * sin(2.0 * mp - m)
+        - 0.00017 * sin(omega)
+        - 0.00007 * sin(mp + 2.0 * m)
+        + 0.00004 * sin(2.0 * mp - 2.0 * f)
+        + 0.00004 * sin(3.0 * m)
+        + 0.00003 * sin(mp + m - 2.0 * f)
+        + 0.00003 * sin(2.0 * mp + 2.0 * f)
+        - 0.00003 * sin(mp + m + 2.0 * f)
+        + 0.00003 * sin(mp - m + 2.0 * f)
+        - 0.00002 * sin(mp - m - 2.0 * f)
+        - 0.00002 * sin(3.0 * mp + m)
+        + 0.00002 * sin(4.0 * mp);
+
+    jde + correction
+}
+
+/// (coefficients of D, M, M', F, sine coefficient of longitude, cosine coefficient of distance)
+const LONGITUDE_DISTANCE_TERMS: [(f64, f64, f64, f64, f64, f64); 24] = [
+    (0.0, 0.0, 1.0, 0.0, 6288774.0, -20905355.0),
+    (2.0, 0.0, -1.0, 0.0, 1274027.0, -3699111.0),
+    (2.0, 0.0, 0.0, 0.0, 658314.0, -2955968.0),
+    (0.0, 0.0, 2.0, 0.0, 213618.0, -569925.0),
+    (0.0, 1.0, 0.0, 0.0, -185116.0, 48888.0),
+    (0.0, 0.0, 0.0, 2.0, -114332.0, -3149.0),
+    (2.0, 0.0, -2.0, 0.0, 58793.0, 246158.0),
+    (2.0, -1.0, -1.0, 0.0, 57066.0, -152138.0),
+    (2.0, 0.0, 1.0, 0.0, 53322.0, -170733.0),
+    (2.0, -1.0, 0.0, 0.0, 45758.0, -204586.0),
+    (0.0, 1.0, -1.0, 0.0, -40923.0, -129620.0),
+    (1.0, 0.0, 0.0, 0.0, -34720.0, 108743.0),
+    (0.0, 1.0, 1.0, 0.0, -30383.0, 104755.0),
+    (2.0, 0.0, 0.0, -2.0, 15327.0, 10321.0),
+    (0.0, 0.0, 1.0, 2.0, -12528.0, 0.0),
+    (0.0, 0.0, 1.0, -2.0, 10980.0, 79661.0),
+    (4.0, 0.0, -1.0, 0.0, 10675.0, -34782.0),
+    (0.0, 0.0, 3.0, 0.0, 10034.0, -23210.0),
+    (4.0, 0.0, -2.0, 0.0, 8548.0, -21636.0),
+    (2.0, 1.0, -1.0, 0.0, -7888.0, 24208.0),
+    (2.0, 1.0, 0.0, 0.0, -6766.0, 30824.0),
+    (1.0, 0.0, -1.0, 0.0, -5163.0, -8379.0),
+    (1.0, 1.0, 0.0, 0.0, 4987.0, -16675.0),
+    (2.0, -1.0, 1.0, 0.0, 4036.0, -12831.0),
+];
+
+/// (coefficients of D, M, M', F, sine coefficient of latitude)
+const LATITUDE_TERMS: [(f64, f64, f64, f64, f64); 13] = [
+    (0.0, 0.0, 0.0, 1.0, 5128122.0),
+    (0.0, 0.0, 1.0, 1.0, 280602.0),
+    (0.0, 0.0, 1.0, -1.0, 277693.0),
+    (2.0, 0.0, 0.0, -1.0, 173237.0),
+    (2.0, 0.0, -1.0, 1.0, 55413.0),
+    (2.0, 0.0, -1.0, -1.0, 46271.0),
+    (2.0, 0.0, 0.0, 1.0, 32573.0),
+    (0.0, 0.0, 2.0, 1.0, 17198.0),
+    (2.0, 0.0, 1.0, -1.0, 9266.0),
+    (0.0, 0.0, 2.0, -1.0, 8822.0),
+    (2.0, -1.0, 0.0, -1.0, 8216.0),
+    (2.0, 0.0, -2.0, -1.0, 4324.0),
+    (2.0, 0.0, 1.0, 1.0, 4200.0),
+];
+
+/// geocentric position of the moon at a julian date, accurate to about 20 arcseconds
+pub fn moon_position(jd: f64) -> MoonPosition {
+    let t = (jd - 2451545.0) / 36525.0;
+
+    let lp = fix_angle(218.3164477 + 481267.88123421 * t - 0.0015786 * t * t);
+    let d = fix_angle(297.8501921 + 445267.1114034 * t - 0.0018819 * t * t);
+    let m = fix_angle(357.5291092 + 35999.0502909 * t - 0.0001536 * t * t);
+    let mp = fix_angle(134.9633964 + 477198.8675055 * t + 0.0087414 * t * t);
+    let f = fix_angle(93.2720950 + 483202.0175233 * t - 0.0036539 * t * t);
+
+    let a1 = fix_angle(119.75 + 131.849 * t);
+    let a2 = fix_angle(53.09 + 479264.290 * t);
+    let a3 = fix_angle(313.45 + 481266.484 * t);
+    let e = 1.0 - 0.002516 * t - 0.0000074 * t * t;
+
+    let eccentricity = |cm: f64| match cm.abs() as u8 {
+        0 => 1.0,
+        1 => e,
+        _ => e * e,
+    };
+
+    let (mut sum_l, mut sum_r) = (0.0, 0.0);
+    for (cd, cm, cmp, cf, l, r) in LONGITUDE_DISTANCE_TERMS {
+        let arg = cd * d + cm * m + cmp * mp + cf * f;
+        sum_l += l * eccentricity(cm) * sin(arg);
+        sum_r += r * eccentricity(cm) * cos(arg);
+    }
+    let mut sum_b = 0.0;
+    for (cd, cm, cmp, cf, b) in LATITUDE_TERMS {
+        sum_b += b * eccentricity(cm) * sin(cd * d + cm * m + cmp * mp + cf * f);
+    }
+
+    sum_l += 3958.0 * sin(a1) + 1962.0 * sin(lp - f) + 318.0 * sin(a2);
+    sum_b += -2235.0 * sin(lp)
+        + 382.0 * sin(a3)
+        + 175.0 * sin(a1 - f)
+        + 175.0 * sin(a1 + f)
+        + 127.0 * sin(lp - mp)
+        - 115.0 * sin(lp + mp);
+
+    let longitude = fix_angle(lp + sum_l / 1_000_000.0);
+    let latitude = sum_b / 1_000_000.0;
+    let distance = 385000.56 + sum_r / 1000.0;
+
+    let obliquity = 23.4392911 - 0.0130042 * t;
+    let right_ascension = fix_angle(arctan2(
+        sin(longitude) * cos(obliquity) - tan(latitude) * sin(obliquity),
+        cos(longitude),
+    ));
+    let declination =
+        arcsin(sin(latitude) * cos(obliquity) + cos(latitude) * sin(obliquity) * sin(longitude));
+
+    MoonPosition {
+        right_ascension,
+        declination,
+        distance,
+    }
+}
+
+/// greenwich mean sidereal time in degrees
+pub fn sidereal_time(jd: f64) -> f64 {
+    let t = (jd - 2451545.0) / 36525.0;
+    fix_angle(
+        280.46061837 + 360.98564736629 * (jd - 2451545.0) + 0.000387933 * t * t
+            - t * t * t / 38710000.0,
+    )
+}
+
+/// geocentric altitude of the moon's center in degrees, ignoring refraction
+pub fn moon_altitude(jd: f64, latitude: f64, longitude: f64) -> f64 {
+    moon_horizontal(jd, latitude, longitude).0
+}
+
+/// geocentric altitude and azimuth ( clockwise from the north ) of the moon's center in degrees, ignoring refraction
+pub fn moon_horizontal(jd: f64, latitude: f64, longitude: f64) -> (f64, f64) {
+    let position = moon_position(jd);
+    let hour_angle = sidereal_time(jd) + longitude - position.right_ascension;
+    horizontal(hour_angle, position.declination, latitude)
+}
+
+/// altitude of the moon's center at the moment of its rising or setting (Meeus ch. 15)
+pub fn moon_horizon_altitude(jd: f64) -> f64 {
+    0.7275 * moon_position(jd).parallax() - 34.0 / 60.0
+}
@@ -7,7 +7,7 @@ pub fn fix_hour(angle: f64) -> f64 {
 }
 
 pub fn fix(mut num: f64, base: f64) -> f64 {
-    num = num % base;
+    num %= base;
 
     if num < 0.0 {
         num + base
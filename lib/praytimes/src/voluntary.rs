@@ -0,0 +1,61 @@
+//! Times of the voluntary prayers and the divisions of the night
+//!
+//! the night starts at sunset and ends at sunrise, or at fajr with [`MidnightMethod::Jafari`], like the midnight
+//! of the calculation. the times are calculated with [`crate::Calculator::voluntary_times`].
+//!
+//! [`MidnightMethod::Jafari`]: crate::types::MidnightMethod::Jafari
+use chrono::NaiveDateTime;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::forbidden::{self, ForbiddenKind, ForbiddenRules};
+use crate::internal_calculator::InternalCalculator;
+
+/// voluntary prayer times and divisions of the night of a day, in utc
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct VoluntaryTimes {
+    /// end of the forbidden interval after sunrise
+    pub ishraq: Option<NaiveDateTime>,
+
+    /// a quarter of the day after sunrise, the preferred time of duha ( it can be prayed from ishraq )
+    pub duha_start: Option<NaiveDateTime>,
+
+    /// start of the zawal
+    pub duha_end: Option<NaiveDateTime>,
+
+    /// end of the first third of the night, start of the middle third
+    pub first_third: Option<NaiveDateTime>,
+
+    /// end of the middle third of the night, start of the last third
+    pub last_third: Option<NaiveDateTime>,
+
+    /// start of the last sixth of the night
+    pub last_sixth: Option<NaiveDateTime>,
+
+    /// best time for tahajjud, the start of the last third of the night
+    pub tahajjud: Option<NaiveDateTime>,
+}
+
+pub(crate) fn times(calculator: &InternalCalculator, rules: &ForbiddenRules) -> VoluntaryTimes {
+    let forbidden = forbidden::intervals(calculator, rules);
+    let find = |kind: ForbiddenKind| forbidden.iter().find(|interval| interval.kind == kind);
+
+    let sunrise = calculator.sunrise();
+    let sunset = calculator.sunset();
+    let day = InternalCalculator::time_difference(sunrise, sunset);
+    let night = calculator.night_length();
+    let night_portion = |portion: f64| calculator.datetime_from_hours(sunset + night * portion);
+    let last_third = night_portion(2.0 / 3.0);
+
+    VoluntaryTimes {
+        ishraq: find(ForbiddenKind::AfterSunrise).map(|interval| interval.end),
+        duha_start: calculator.datetime_from_hours(sunrise + day / 4.0),
+        duha_end: find(ForbiddenKind::Zawal).map(|interval| interval.start),
+        first_third: night_portion(1.0 / 3.0),
+        last_third,
+        last_sixth: night_portion(5.0 / 6.0),
+        tahajjud: last_third,
+    }
+}
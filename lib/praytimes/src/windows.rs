@@ -0,0 +1,146 @@
+//! Start and end of the obligatory prayers
+//!
+//! each prayer can be prayed from its time until the end given by [`WindowRules`], and a madhab prefers the
+//! beginning of some windows ( the ikhtiyari time ). the windows are calculated with
+//! [`crate::Calculator::prayer_windows`].
+use chrono::NaiveDateTime;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::forbidden::{ForbiddenLimit, ForbiddenRules};
+use crate::types::PraytimesOutput;
+
+/// end of asr
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum AsrEnd {
+    /// sunset ( default )
+    #[default]
+    Sunset,
+    /// the yellowing of the sun, see [`WindowRules::yellowing`]
+    Yellowing,
+}
+
+/// end of isha
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum IshaEnd {
+    /// the midnight of the parameters
+    Midnight,
+    /// the fajr of the next day ( default )
+    #[default]
+    Fajr,
+}
+
+/// school of jurisprudence defining the preferred ( ikhtiyari ) windows
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Madhab {
+    Hanafi,
+    Maliki,
+    Shafii,
+    Hanbali,
+}
+
+/// how the windows end
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowRules {
+    pub asr_end: AsrEnd,
+    pub isha_end: IshaEnd,
+    /// when the sun turns yellow before sunset, like the forbidden interval before sunset ( default is 15 minutes )
+    pub yellowing: ForbiddenLimit,
+    /// madhab of the preferred windows, none by default
+    pub madhab: Option<Madhab>,
+}
+
+impl Default for WindowRules {
+    fn default() -> Self {
+        Self {
+            asr_end: AsrEnd::default(),
+            isha_end: IshaEnd::default(),
+            yellowing: ForbiddenRules::default().before_sunset,
+            madhab: None,
+        }
+    }
+}
+
+/// time of a prayer, in utc
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Window {
+    pub start: Option<NaiveDateTime>,
+    pub end: Option<NaiveDateTime>,
+    /// end of the preferred ( ikhtiyari ) part of the window when the madhab defines one, only for asr and isha:
+    /// the whole window of dhuhr is preferred, the preferred fajr ends when the light spreads ( isfar ) which has no
+    /// angle of the sun to calculate it, and maghrib is preferred right at its start without an end of its own
+    pub preferred_end: Option<NaiveDateTime>,
+}
+
+/// windows of the obligatory prayers of a day
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct PrayerWindows {
+    pub fajr: Window,
+    pub dhuhr: Window,
+    pub asr: Window,
+    pub maghrib: Window,
+    pub isha: Window,
+}
+
+/// instants the windows depend on, besides the prayer times
+pub(crate) struct WindowBounds {
+    /// fajr of the next day
+    pub next_fajr: Option<NaiveDateTime>,
+    /// when the shadow is twice its length plus the noon shadow
+    pub double_shadow: Option<NaiveDateTime>,
+    pub yellowing: Option<NaiveDateTime>,
+    /// end of the first third of the night
+    pub first_third: Option<NaiveDateTime>,
+}
+
+pub(crate) fn windows(
+    times: &PraytimesOutput,
+    bounds: &WindowBounds,
+    rules: &WindowRules,
+) -> PrayerWindows {
+    let window = |start, end| Window {
+        start,
+        end,
+        preferred_end: None,
+    };
+
+    let asr_preferred_end = match rules.madhab {
+        Some(Madhab::Shafii | Madhab::Hanbali) => bounds.double_shadow,
+        Some(Madhab::Hanafi | Madhab::Maliki) => bounds.yellowing,
+        None => None,
+    };
+    let isha_preferred_end = rules.madhab.and(bounds.first_third);
+
+    PrayerWindows {
+        fajr: window(times.fajr, times.sunrise),
+        dhuhr: window(times.dhuhr, times.asr),
+        asr: Window {
+            preferred_end: asr_preferred_end,
+            ..window(
+                times.asr,
+                match rules.asr_end {
+                    AsrEnd::Sunset => times.sunset,
+                    AsrEnd::Yellowing => bounds.yellowing,
+                },
+            )
+        },
+        maghrib: window(times.maghrib, times.isha),
+        isha: Window {
+            preferred_end: isha_preferred_end,
+            ..window(
+                times.isha,
+                match rules.isha_end {
+                    IshaEnd::Midnight => times.midnight,
+                    IshaEnd::Fajr => bounds.next_fajr,
+                },
+            )
+        },
+    }
+}
@@ -0,0 +1,181 @@
+//! Prayer times for a local civil day in a time zone ( requires the `chrono-tz` feature )
+//!
+//! [`PraytimesOutput`](crate::types::PraytimesOutput) is computed for a day of the calculation and holds utc times, so around
+//! midnight in zones far from their longitude ( UTC+12, UTC-10, ... ) the events may belong to
+//! another local day. [`Calculator::calculate_in`] picks the times of the local day instead.
+//!
+//! ```rust
+//! use chrono::NaiveDate;
+//! use praytimes::{methods, types::Location, zoned::Tz, Calculator};
+//!
+//! let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+//! let auckland = Location {
+//!     latitude: -36.85,
+//!     longitude: 174.76,
+//!     ..Default::default()
+//! };
+//! let date = NaiveDate::from_ymd_opt(2023, 9, 24).unwrap();
+//! let times = calculator.calculate_in(&auckland, &date, &Tz::Pacific__Auckland);
+//!
+//! assert_eq!(times.dhuhr.unwrap().date_naive(), date);
+//! assert!(times.has_transition());
+//! ```
+use std::fmt::Display;
+
+use chrono::{DateTime, Duration, LocalResult, NaiveDate, NaiveDateTime, Offset, TimeZone};
+
+pub use chrono_tz::Tz;
+
+use crate::{
+    hijri::HijriDate,
+    types::{FormattedTimes, Location, PraytimeType, PraytimesOutput},
+    Calculator,
+};
+
+/// calculated prayertimes of a local civil day, in a time zone
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZonedPraytimes<Z: TimeZone> {
+    /// the local day
+    pub date: NaiveDate,
+
+    /// first instant of the local day
+    pub day_start: DateTime<Z>,
+
+    /// first instant of the next local day, 23 or 25 hours after `day_start` on transition days
+    pub day_end: DateTime<Z>,
+
+    pub imsak: Option<DateTime<Z>>,
+    pub fajr: Option<DateTime<Z>>,
+    pub sunrise: Option<DateTime<Z>>,
+    pub dhuhr: Option<DateTime<Z>>,
+    pub asr: Option<DateTime<Z>>,
+    pub sunset: Option<DateTime<Z>>,
+    pub maghrib: Option<DateTime<Z>>,
+    pub isha: Option<DateTime<Z>>,
+
+    /// the middle of the night following this day, usually after the local midnight
+    pub midnight: Option<DateTime<Z>>,
+
+    /// the local day in the hijri calendar ( see [`Calculator::with_hijri_calendar`] )
+    pub hijri: Option<HijriDate>,
+
+    /// events adjusted for the high latitudes
+    pub adjusted: Vec<PraytimeType>,
+}
+
+impl<Z: TimeZone> ZonedPraytimes<Z> {
+    /// whether the utc offset of the zone changes during this day ( daylight saving time transition )
+    pub fn has_transition(&self) -> bool {
+        self.day_start.offset().fix() != self.day_end.offset().fix()
+    }
+
+    /// whether the zone skips this local day, like Pacific/Apia on 2011-12-30 when it crossed the date line. the day
+    /// has no length and no events
+    pub fn is_skipped(&self) -> bool {
+        self.day_start == self.day_end
+    }
+
+    /// length of the local day, 24 hours except on transition days
+    pub fn day_length(&self) -> Duration {
+        self.day_end.clone() - self.day_start.clone()
+    }
+
+    /// format times with a given format ( see [chrono's format docs for more information ](https://docs.rs/chrono/latest/chrono/format/strftime/index.html) )
+    pub fn format_times(&self, format: &str) -> FormattedTimes
+    where
+        Z::Offset: Display,
+    {
+        let format_time =
+            |time: &Option<DateTime<Z>>| time.as_ref().map(|t| t.format(format).to_string());
+        FormattedTimes {
+            imsak: format_time(&self.imsak),
+            fajr: format_time(&self.fajr),
+            sunrise: format_time(&self.sunrise),
+            dhuhr: format_time(&self.dhuhr),
+            asr: format_time(&self.asr),
+            sunset: format_time(&self.sunset),
+            maghrib: format_time(&self.maghrib),
+            isha: format_time(&self.isha),
+            midnight: format_time(&self.midnight),
+            adjusted: self.adjusted.clone(),
+        }
+    }
+}
+
+/// first instant of a local day, skipping the missing hour when the day starts in a gap
+fn start_of_day<Z: TimeZone>(date: &NaiveDate, zone: &Z) -> Option<DateTime<Z>> {
+    let midnight = date.and_hms_opt(0, 0, 0)?;
+    (0..=24 * 60)
+        .map(|minutes| zone.from_local_datetime(&(midnight + Duration::minutes(minutes))))
+        .find_map(|local| match local {
+            LocalResult::Single(time) => Some(time),
+            LocalResult::Ambiguous(earliest, _) => Some(earliest),
+            LocalResult::None => None,
+        })
+}
+
+impl Calculator {
+    /// Calculate the prayertimes of a local civil day in a time zone
+    ///
+    /// the day whose dhuhr falls on `date` in the zone is used, so all the events of the output belong
+    /// to the same solar day, the night events ( isha and midnight ) may be after the local midnight. the times are
+    /// rounded on the minutes of the local clock ( see [`crate::types::Rounding::round_in`] ). a day skipped by the
+    /// zone has no events ( see [`ZonedPraytimes::is_skipped`] )
+    pub fn calculate_in<Z: TimeZone>(
+        &self,
+        location: &Location,
+        date: &NaiveDate,
+        zone: &Z,
+    ) -> ZonedPraytimes<Z> {
+        let local_date = |time: &NaiveDateTime| zone.from_utc_datetime(time).date_naive();
+        // rounded on the local clock, whose offset can change during the day
+        let cache = self.day_cache();
+        let calculate = |day: &NaiveDate| {
+            self.calculate_from(location, day, self.hijri_of(day, None), &cache, |time| {
+                zone.offset_from_utc_datetime(time).fix()
+            })
+        };
+
+        let day_start = start_of_day(date, zone).expect("every day has a start");
+        let day_end = date
+            .succ_opt()
+            .and_then(|next| start_of_day(&next, zone))
+            .unwrap_or_else(|| day_start.clone() + Duration::days(1));
+
+        // the first instant of a skipped day is the start of the next day
+        let (day, times) = if day_start.date_naive() != *date {
+            (*date, PraytimesOutput::default())
+        } else {
+            [Some(*date), date.pred_opt(), date.succ_opt()]
+                .into_iter()
+                .flatten()
+                .map(|day| (day, calculate(&day)))
+                .find(|(_, times)| times.dhuhr.map(|d| local_date(&d)) == Some(*date))
+                .unwrap_or_else(|| (*date, calculate(date)))
+        };
+
+        let to_zone = |time: Option<NaiveDateTime>| time.map(|t| zone.from_utc_datetime(&t));
+
+        ZonedPraytimes {
+            date: *date,
+            day_start,
+            day_end,
+            imsak: to_zone(times.imsak),
+            fajr: to_zone(times.fajr),
+            sunrise: to_zone(times.sunrise),
+            dhuhr: to_zone(times.dhuhr),
+            asr: to_zone(times.asr),
+            sunset: to_zone(times.sunset),
+            maghrib: to_zone(times.maghrib),
+            isha: to_zone(times.isha),
+            midnight: to_zone(times.midnight),
+            // the hijri date of the calculated day, converted again when it's the day before or after
+            hijri: if day == *date {
+                times.hijri
+            } else {
+                self.hijri_date(date)
+            },
+            adjusted: times.adjusted,
+        }
+    }
+}
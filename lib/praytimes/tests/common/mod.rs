@@ -0,0 +1,21 @@
+//! The fixtures shared by the tests, every test crate uses a part of them
+#![allow(dead_code)]
+
+use chrono::NaiveDate;
+use praytimes::{methods, types::Location, Calculator};
+
+pub fn tehran() -> Location {
+    Location {
+        latitude: 35.7,
+        longitude: 51.4,
+        ..Default::default()
+    }
+}
+
+pub fn date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2023, 6, 1).unwrap()
+}
+
+pub fn calculator() -> Calculator {
+    Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default())
+}
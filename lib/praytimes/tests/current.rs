@@ -0,0 +1,125 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use praytimes::{
+    methods,
+    types::{HighLatsMethod, Location, Parameters, PraytimeType},
+    windows::Window,
+    Calculator,
+};
+
+mod common;
+use common::{calculator, date, tehran};
+
+fn window(start: Option<NaiveDateTime>, end: Option<NaiveDateTime>) -> Window {
+    Window {
+        start,
+        end,
+        preferred_end: None,
+    }
+}
+
+#[test]
+fn should_find_the_window_of_the_instant() {
+    let times = calculator().calculate(&tehran(), &date());
+    let instant = times.asr.unwrap() + Duration::minutes(10);
+    let now = calculator().current_and_next(&tehran(), &instant);
+
+    assert_eq!(now.instant, instant);
+    assert_eq!(
+        now.current,
+        Some((PraytimeType::Asr, window(times.asr, times.sunset)))
+    );
+    assert_eq!(
+        now.next,
+        Some((PraytimeType::Sunset, times.sunset.unwrap()))
+    );
+    assert_eq!(now.remaining(), Some(times.sunset.unwrap() - instant));
+
+    // an event starts its window, and is the next event at its time
+    let now = calculator().current_and_next(&tehran(), &times.asr.unwrap());
+    assert_eq!(now.current.unwrap().0, PraytimeType::Asr);
+    assert_eq!(now.next, Some((PraytimeType::Asr, times.asr.unwrap())));
+    assert_eq!(now.remaining(), Some(Duration::zero()));
+}
+
+#[test]
+fn should_only_be_in_the_obligatory_prayers() {
+    let times = calculator().calculate(&tehran(), &date());
+
+    // from sunrise to dhuhr
+    let now = calculator().current_and_next(&tehran(), &times.sunrise.unwrap());
+    assert_eq!(now.current, None);
+    assert_eq!(
+        now.next,
+        Some((PraytimeType::Sunrise, times.sunrise.unwrap()))
+    );
+
+    // maghrib at sunset starts its window instead of the sunset
+    let now = calculator().current_and_next(&tehran(), &times.sunset.unwrap());
+    assert_eq!(
+        now.current,
+        Some((PraytimeType::Maghrib, window(times.maghrib, times.isha)))
+    );
+}
+
+#[test]
+fn should_cross_the_day_boundaries() {
+    let times = calculator().calculate(&tehran(), &date());
+    let next = calculator().calculate(&tehran(), &date().succ_opt().unwrap());
+
+    // midnight of tehran is before midnight utc, the next event is the imsak of the next day
+    // isha ends at the fajr of the next day
+    let instant = times.midnight.unwrap() + Duration::minutes(1);
+    let now = calculator().current_and_next(&tehran(), &instant);
+    assert_eq!(
+        now.current,
+        Some((PraytimeType::Isha, window(times.isha, next.fajr)))
+    );
+    assert_eq!(now.next, Some((PraytimeType::Imsak, next.imsak.unwrap())));
+
+    // the fajr of the next day is before midnight utc
+    let instant = date().succ_opt().unwrap().and_hms_opt(0, 0, 0).unwrap();
+    let now = calculator().current_and_next(&tehran(), &instant);
+    assert_eq!(
+        now.current,
+        Some((PraytimeType::Fajr, window(next.fajr, next.sunrise)))
+    );
+    assert_eq!(
+        now.next,
+        Some((PraytimeType::Sunrise, next.sunrise.unwrap()))
+    );
+}
+
+#[test]
+fn should_skip_the_missing_events() {
+    let tromso = Location {
+        latitude: 69.65,
+        longitude: 18.96,
+        ..Default::default()
+    };
+    let calculator = Calculator::new(
+        Parameters {
+            high_latitudes: HighLatsMethod::None,
+            ..methods::MUSLIM_WORLD_LEAGUE
+        },
+        Default::default(),
+    );
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+    let times = calculator.calculate(&tromso, &date);
+    assert_eq!(times.sunset, None);
+
+    // asr doesn't end without a sunset
+    let instant: NaiveDateTime = times.asr.unwrap() + Duration::hours(1);
+    let now = calculator.current_and_next(&tromso, &instant);
+    assert_eq!(now.current, None);
+    let next = calculator.calculate(&tromso, &date.succ_opt().unwrap());
+    assert_eq!(next.dhuhr, now.next.map(|(_, time)| time));
+
+    // in the polar night the dhuhr of the day before isn't current before dhuhr
+    let date = NaiveDate::from_ymd_opt(2023, 12, 21).unwrap();
+    let times = calculator.calculate(&tromso, &date);
+    assert_eq!(times.sunrise, None);
+    let instant = times.dhuhr.unwrap() - Duration::hours(1);
+    let now = calculator.current_and_next(&tromso, &instant);
+    assert_eq!(now.current, None);
+    assert_eq!(now.next, Some((PraytimeType::Dhuhr, times.dhuhr.unwrap())));
+}
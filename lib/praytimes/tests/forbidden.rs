@@ -0,0 +1,79 @@
+use chrono::{Duration, NaiveDate};
+use praytimes::{
+    forbidden::{ForbiddenKind, ForbiddenLimit, ForbiddenRules, ZawalWindow},
+    methods,
+    types::{Degrees, Location, Minutes},
+    Calculator,
+};
+
+mod common;
+use common::tehran;
+
+#[test]
+fn should_surround_sunrise_transit_and_sunset() {
+    let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    let times = calculator.calculate(&tehran(), &date);
+    let intervals = calculator.forbidden_intervals(&tehran(), &date, &ForbiddenRules::default());
+
+    let kinds: Vec<_> = intervals.iter().map(|interval| interval.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ForbiddenKind::AfterSunrise,
+            ForbiddenKind::Zawal,
+            ForbiddenKind::BeforeSunset
+        ]
+    );
+
+    let [after_sunrise, zawal, before_sunset] = [intervals[0], intervals[1], intervals[2]];
+    assert_eq!(Some(after_sunrise.start), times.sunrise);
+    assert_eq!(
+        after_sunrise.end - after_sunrise.start,
+        Duration::minutes(15)
+    );
+    assert_eq!(Some(before_sunset.end), times.sunset);
+    assert_eq!(
+        before_sunset.end - before_sunset.start,
+        Duration::minutes(15)
+    );
+
+    // about a minute on each side of the transit
+    let dhuhr = times.dhuhr.unwrap();
+    let half = (dhuhr - zawal.start).num_milliseconds() as f64 / 60000.0;
+    assert!((1.0..1.3).contains(&half), "{half}");
+    assert!(((zawal.end - dhuhr).num_milliseconds() as f64 / 60000.0 - half).abs() < 0.01);
+}
+
+#[test]
+fn should_use_the_altitude_of_the_sun() {
+    let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    let rules = ForbiddenRules {
+        after_sunrise: ForbiddenLimit::Altitude(Degrees { degree: 5.0 }),
+        zawal: ZawalWindow::Minutes(Minutes { minutes: 5.0 }),
+        before_sunset: ForbiddenLimit::Altitude(Degrees { degree: 5.0 }),
+    };
+    let intervals = calculator.forbidden_intervals(&tehran(), &date, &rules);
+
+    for time in [intervals[0].end, intervals[2].start] {
+        let altitude = calculator.sun_coordinates(&tehran(), &time).altitude;
+        assert!((altitude - 5.0).abs() < 0.05, "{altitude}");
+    }
+    assert_eq!(intervals[1].end - intervals[1].start, Duration::minutes(10));
+}
+
+#[test]
+fn should_skip_the_intervals_without_sunrise() {
+    let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    let svalbard = Location {
+        latitude: 78.22,
+        longitude: 15.65,
+        ..Default::default()
+    };
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+    let intervals = calculator.forbidden_intervals(&svalbard, &date, &ForbiddenRules::default());
+
+    assert_eq!(intervals.len(), 1);
+    assert_eq!(intervals[0].kind, ForbiddenKind::Zawal);
+}
@@ -0,0 +1,146 @@
+use chrono::{Duration, NaiveDate};
+use praytimes::{
+    methods,
+    timetable::DateRange,
+    types::{HighLatsMethod, Location, Parameters, PraytimeType, PraytimesOutput, Refinement},
+    Calculator,
+};
+
+fn calculator(high_latitudes: HighLatsMethod) -> Calculator {
+    Calculator::new(
+        Parameters {
+            high_latitudes,
+            ..methods::MUSLIM_WORLD_LEAGUE
+        },
+        Default::default(),
+    )
+}
+
+fn location(latitude: f64, longitude: f64) -> Location {
+    Location {
+        latitude,
+        longitude,
+        ..Default::default()
+    }
+}
+
+fn minutes(duration: Duration) -> f64 {
+    duration.num_seconds() as f64 / 60.0
+}
+
+#[test]
+fn should_use_the_seasonal_limits_of_the_moonsighting_committee() {
+    let london = location(51.5, -0.13);
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+    let times = calculator(HighLatsMethod::MoonsightingCommittee).calculate(&london, &date);
+
+    // 182 days after the winter solstice
+    let fajr = minutes(times.sunrise.unwrap() - times.fajr.unwrap());
+    assert!((fajr - 119.7).abs() < 0.5, "{fajr}");
+    let isha = minutes(times.isha.unwrap() - times.sunset.unwrap());
+    assert!((isha - 80.4).abs() < 0.5, "{isha}");
+    // imsak follows the adjusted fajr
+    assert_eq!(
+        times.adjusted,
+        vec![PraytimeType::Imsak, PraytimeType::Fajr, PraytimeType::Isha]
+    );
+}
+
+#[test]
+fn should_use_the_seventh_of_the_night_above_55_degrees() {
+    let edinburgh = location(55.95, -3.19);
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+    let times = calculator(HighLatsMethod::MoonsightingCommittee).calculate(&edinburgh, &date);
+    let one_seventh = calculator(HighLatsMethod::OneSeventh).calculate(&edinburgh, &date);
+
+    assert_eq!(times.fajr, one_seventh.fajr);
+    assert_eq!(times.isha, one_seventh.isha);
+}
+
+#[test]
+fn should_keep_the_portion_of_the_night_of_the_nearest_latitude() {
+    let oslo = location(59.91, 10.75);
+    let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    let times = calculator(HighLatsMethod::NearestLatitude).calculate(&oslo, &date);
+    let unadjusted = calculator(HighLatsMethod::None).calculate(&oslo, &date);
+
+    assert_eq!(unadjusted.fajr, None);
+    assert!(times.fajr.unwrap() < times.sunrise.unwrap());
+    assert!(times.isha.unwrap() > times.sunset.unwrap());
+    assert!(times.isha.unwrap() < times.fajr.unwrap() + Duration::days(1));
+    assert_eq!(
+        times.adjusted,
+        vec![PraytimeType::Imsak, PraytimeType::Fajr, PraytimeType::Isha]
+    );
+
+    // below 48.5° nothing changes
+    let tehran = location(35.7, 51.4);
+    assert_eq!(
+        calculator(HighLatsMethod::NearestLatitude).calculate(&tehran, &date),
+        calculator(HighLatsMethod::None).calculate(&tehran, &date)
+    );
+}
+
+#[test]
+fn should_use_the_twilight_of_the_nearest_day() {
+    let oslo = location(59.91, 10.75);
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+    let times = calculator(HighLatsMethod::NearestDay).calculate(&oslo, &date);
+    assert_eq!(
+        times.adjusted,
+        vec![PraytimeType::Imsak, PraytimeType::Fajr, PraytimeType::Isha]
+    );
+
+    // the nearest day with a fajr
+    let unadjusted = calculator(HighLatsMethod::None);
+    let nearest = (1..183)
+        .flat_map(|days| [date - Duration::days(days), date + Duration::days(days)])
+        .map(|day| unadjusted.calculate(&oslo, &day))
+        .find(|times| times.fajr.is_some())
+        .unwrap();
+
+    // the same portion of the night
+    let portion = |times: &PraytimesOutput| {
+        let night = 1440.0 - minutes(times.sunset.unwrap() - times.sunrise.unwrap());
+        minutes(times.sunrise.unwrap() - times.fajr.unwrap()) / night
+    };
+    assert!((portion(&times) - portion(&nearest)).abs() < 0.001);
+
+    // the days with a normal twilight are not adjusted
+    let winter = NaiveDate::from_ymd_opt(2023, 12, 21).unwrap();
+    let times = calculator(HighLatsMethod::NearestDay).calculate(&oslo, &winter);
+    assert_eq!(times, unadjusted.calculate(&oslo, &winter));
+    assert!(times.adjusted.is_empty());
+}
+
+#[test]
+fn should_keep_the_nearest_day_twilight_in_the_night() {
+    let reykjavik = location(64.1, -21.9);
+    let calculator = calculator(HighLatsMethod::NearestDay);
+    let date = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+    let times = calculator.calculate(&reykjavik, &date);
+    let next_day = calculator.calculate(&reykjavik, &date.succ_opt().unwrap());
+
+    // the nearest night with a twilight is much longer
+    assert!(times.fajr.unwrap() < times.sunrise.unwrap());
+    assert!(times.sunset.unwrap() < times.isha.unwrap());
+    assert!(times.isha.unwrap() < next_day.fajr.unwrap());
+    assert!(next_day.fajr.unwrap() < next_day.sunrise.unwrap());
+}
+
+#[test]
+fn should_search_the_nearest_day_whatever_the_refinement_before() {
+    let reykjavik = location(64.1, -21.9);
+    let converge = Refinement::Converge {
+        tolerance: 1.0,
+        max_iterations: 10,
+    };
+    let calculator = calculator(HighLatsMethod::NearestDay).with_refinement(converge);
+    let date = NaiveDate::from_ymd_opt(2024, 4, 10).unwrap();
+    let times = calculator.calculate(&reykjavik, &date);
+
+    // the portions of the nearest days don't depend on the days calculated before
+    let range = DateRange::new(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), date);
+    let row = calculator.timetable(&reykjavik, &range).rows.pop().unwrap();
+    assert_eq!(row.times, times);
+}
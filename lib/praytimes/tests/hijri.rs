@@ -0,0 +1,152 @@
+use chrono::NaiveDate;
+use praytimes::{
+    hijri::{Calendar, Epoch, HijriDate, LeapYears, TABULAR, UMM_AL_QURA},
+    methods,
+    types::Location,
+    Calculator,
+};
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn hijri(year: i32, month: u32, day: u32) -> HijriDate {
+    HijriDate { year, month, day }
+}
+
+#[test]
+fn should_match_official_umm_al_qura_month_starts() {
+    let cases = [
+        (date(2023, 3, 23), hijri(1444, 9, 1)),
+        (date(2023, 7, 19), hijri(1445, 1, 1)),
+        (date(2024, 3, 11), hijri(1445, 9, 1)),
+        (date(2024, 4, 10), hijri(1445, 10, 1)),
+        (date(2025, 3, 1), hijri(1446, 9, 1)),
+        (date(2025, 6, 26), hijri(1447, 1, 1)),
+    ];
+
+    for (gregorian, expected) in cases {
+        assert_eq!(UMM_AL_QURA.to_hijri(&gregorian), Some(expected));
+        assert_eq!(UMM_AL_QURA.from_hijri(&expected), Some(gregorian));
+    }
+}
+
+#[test]
+fn should_round_trip_umm_al_qura() {
+    let mut day = date(2020, 1, 1);
+    while day < date(2022, 1, 1) {
+        let converted = UMM_AL_QURA.to_hijri(&day).unwrap();
+        assert_eq!(UMM_AL_QURA.from_hijri(&converted), Some(day));
+        day = day.succ_opt().unwrap();
+    }
+}
+
+#[test]
+fn should_have_umm_al_qura_months_of_29_or_30_days() {
+    for year in 1420..1500 {
+        for month in 1..=12 {
+            let length = UMM_AL_QURA.month_length(year, month).unwrap();
+            assert!(
+                length == 29 || length == 30,
+                "{year}/{month} has {length} days"
+            );
+
+            let start = UMM_AL_QURA.from_hijri(&hijri(year, month, 1)).unwrap();
+            let next = match month {
+                12 => hijri(year + 1, 1, 1),
+                _ => hijri(year, month + 1, 1),
+            };
+            assert_eq!(
+                UMM_AL_QURA.from_hijri(&next),
+                start.checked_add_signed(chrono::Duration::days(length.into()))
+            );
+        }
+    }
+}
+
+#[test]
+fn should_start_tabular_calendar_at_its_epoch() {
+    assert_eq!(TABULAR.to_hijri(&date(622, 7, 19)), Some(hijri(1, 1, 1)));
+    assert_eq!(TABULAR.to_hijri(&date(622, 7, 18)), Some(hijri(0, 12, 29)));
+
+    let astronomical = praytimes::hijri::HijriCalendar {
+        calendar: Calendar::Tabular {
+            leap_years: LeapYears::Type2,
+            epoch: Epoch::Astronomical,
+        },
+        adjustment: 0,
+    };
+    assert_eq!(
+        astronomical.to_hijri(&date(622, 7, 18)),
+        Some(hijri(1, 1, 1))
+    );
+}
+
+#[test]
+fn should_round_trip_tabular() {
+    for leap_years in [
+        LeapYears::Type1,
+        LeapYears::Type2,
+        LeapYears::Type3,
+        LeapYears::Type4,
+    ] {
+        let calendar = praytimes::hijri::HijriCalendar {
+            calendar: Calendar::Tabular {
+                leap_years,
+                epoch: Epoch::Civil,
+            },
+            adjustment: 0,
+        };
+        let mut day = date(1990, 1, 1);
+        while day < date(2030, 1, 1) {
+            let converted = calendar.to_hijri(&day).unwrap();
+            assert_eq!(calendar.from_hijri(&converted), Some(day));
+            day = day.succ_opt().unwrap();
+        }
+    }
+}
+
+#[test]
+fn should_reject_missing_days() {
+    // tabular Muharram always has 30 days and Safar 29
+    assert!(TABULAR.from_hijri(&hijri(1445, 2, 30)).is_none());
+    assert!(TABULAR.from_hijri(&hijri(1445, 13, 1)).is_none());
+    assert_eq!(TABULAR.month_length(1445, 1), Some(30));
+    assert_eq!(TABULAR.month_length(1445, 2), Some(29));
+}
+
+#[test]
+fn should_apply_adjustment() {
+    let calendar = UMM_AL_QURA.with_adjustment(-1);
+    assert_eq!(
+        calendar.to_hijri(&date(2024, 3, 12)),
+        Some(hijri(1445, 9, 1))
+    );
+    assert_eq!(
+        calendar.from_hijri(&hijri(1445, 9, 1)),
+        Some(date(2024, 3, 12))
+    );
+}
+
+#[test]
+fn should_add_hijri_date_to_output() {
+    let location = Location {
+        latitude: 21.4225,
+        longitude: 39.8262,
+        elevation: 0.0,
+        atmosphere: None,
+    };
+    let calculator = Calculator::new(methods::UMM_AL_QURA_UNIVERSITY_MAKKAH, Default::default());
+
+    assert_eq!(
+        calculator.calculate(&location, &date(2024, 3, 11)).hijri,
+        None
+    );
+    assert_eq!(
+        calculator
+            .with_hijri_calendar(UMM_AL_QURA)
+            .calculate(&location, &date(2024, 3, 11))
+            .hijri,
+        Some(hijri(1445, 9, 1))
+    );
+}
@@ -0,0 +1,99 @@
+use std::borrow::Cow;
+
+use chrono::NaiveDate;
+use praytimes::{
+    error::CalcError,
+    hijri::UMM_AL_QURA,
+    methods,
+    types::{CalculationUnit, Degrees, HijriRule, Location, Minutes, Parameters, PraytimeType},
+    Calculator,
+};
+
+fn makkah() -> Location {
+    Location {
+        latitude: 21.4225,
+        longitude: 39.8262,
+        ..Default::default()
+    }
+}
+
+fn isha_after_maghrib(calculator: &Calculator, date: &NaiveDate) -> i64 {
+    let times = calculator.calculate(&makkah(), date);
+    (times.isha.unwrap() - times.maghrib.unwrap()).num_minutes()
+}
+
+#[test]
+fn should_delay_isha_of_umm_al_qura_in_ramadan() {
+    let calculator = Calculator::new(methods::UMM_AL_QURA_UNIVERSITY_MAKKAH, Default::default());
+
+    // 1 ramadan 1445 is 2024-03-11
+    let shaban = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+    let ramadan = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+    let shawwal = NaiveDate::from_ymd_opt(2024, 4, 10).unwrap();
+    assert_eq!(isha_after_maghrib(&calculator, &shaban), 90);
+    assert_eq!(isha_after_maghrib(&calculator, &ramadan), 120);
+    assert_eq!(isha_after_maghrib(&calculator, &shawwal), 90);
+
+    // the months follow the hijri calendar of the calculator
+    let adjusted = calculator.with_hijri_calendar(UMM_AL_QURA.with_adjustment(-1));
+    assert_eq!(isha_after_maghrib(&adjusted, &ramadan), 90);
+}
+
+#[test]
+fn should_apply_the_rules_of_the_month_only() {
+    let params = Parameters {
+        hijri_rules: Cow::Owned(vec![
+            HijriRule {
+                month: 9,
+                event: PraytimeType::Fajr,
+                unit: CalculationUnit::Degrees(Degrees { degree: 20.0 }),
+            },
+            HijriRule {
+                month: 12,
+                event: PraytimeType::Dhuhr,
+                unit: CalculationUnit::Minutes(Minutes { minutes: 5.0 }),
+            },
+        ]),
+        ..methods::MUSLIM_WORLD_LEAGUE
+    };
+
+    let ramadan = params.for_hijri_month(9);
+    assert_eq!(
+        ramadan.fajr,
+        CalculationUnit::Degrees(Degrees { degree: 20.0 })
+    );
+    assert_eq!(ramadan.dhuhr, methods::MUSLIM_WORLD_LEAGUE.dhuhr);
+    assert!(ramadan.hijri_rules.is_empty());
+
+    let dhu_al_hijjah = params.for_hijri_month(12);
+    assert_eq!(dhu_al_hijjah.fajr, methods::MUSLIM_WORLD_LEAGUE.fajr);
+    assert_eq!(dhu_al_hijjah.dhuhr, Minutes { minutes: 5.0 });
+}
+
+#[test]
+fn should_reject_invalid_rules() {
+    let with_rule = |month, event| Parameters {
+        hijri_rules: Cow::Owned(vec![HijriRule {
+            month,
+            event,
+            unit: CalculationUnit::Minutes(Minutes { minutes: 120.0 }),
+        }]),
+        ..methods::MUSLIM_WORLD_LEAGUE
+    };
+
+    assert_eq!(with_rule(9, PraytimeType::Isha).validate(), Ok(()));
+    assert_eq!(
+        with_rule(13, PraytimeType::Isha).validate(),
+        Err(CalcError::InvalidHijriRule {
+            month: 13,
+            event: PraytimeType::Isha
+        })
+    );
+    assert_eq!(
+        with_rule(9, PraytimeType::Sunrise).validate(),
+        Err(CalcError::InvalidHijriRule {
+            month: 9,
+            event: PraytimeType::Sunrise
+        })
+    );
+}
@@ -0,0 +1,111 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use praytimes::{
+    methods,
+    types::{HighLatsMethod, Location, Parameters, RoundingPolicy, TuneOffsets},
+    Calculator,
+};
+
+fn istanbul() -> Location {
+    Location {
+        latitude: 41.01,
+        longitude: 28.98,
+        ..Default::default()
+    }
+}
+
+fn julian_from_datetime(datetime: &NaiveDateTime) -> f64 {
+    2440587.5 + datetime.timestamp_millis() as f64 / 86_400_000.0
+}
+
+fn julian_day(date: &NaiveDate) -> f64 {
+    julian_from_datetime(&date.and_hms_opt(12, 0, 0).unwrap())
+}
+
+fn assert_same_time(hours: Option<f64>, time: Option<NaiveDateTime>, date: &NaiveDate) {
+    let start = date.and_hms_opt(0, 0, 0).unwrap();
+    let hours = hours.unwrap();
+    let milliseconds = (time.unwrap() - start).num_milliseconds() as f64;
+    assert!(
+        (hours * 3_600_000.0 - milliseconds).abs() <= 2.0,
+        "{hours} hours instead of {milliseconds} milliseconds"
+    );
+}
+
+#[test]
+fn should_be_the_times_of_calculate_in_hours() {
+    let calculator = Calculator::new(
+        methods::DIYANET_ISLERI_BASKANLIGI,
+        TuneOffsets {
+            fajr: Some(-3.0),
+            isha: Some(5.5),
+            ..Default::default()
+        },
+    )
+    // the hours aren't rounded
+    .with_rounding(RoundingPolicy::NONE);
+    let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+
+    let hours = calculator
+        .calculate_hours(&istanbul(), julian_day(&date))
+        .unwrap();
+    let times = calculator.calculate(&istanbul(), &date);
+
+    assert_same_time(hours.imsak, times.imsak, &date);
+    assert_same_time(hours.fajr, times.fajr, &date);
+    assert_same_time(hours.sunrise, times.sunrise, &date);
+    assert_same_time(hours.dhuhr, times.dhuhr, &date);
+    assert_same_time(hours.asr, times.asr, &date);
+    assert_same_time(hours.sunset, times.sunset, &date);
+    assert_same_time(hours.maghrib, times.maghrib, &date);
+    assert_same_time(hours.isha, times.isha, &date);
+    assert_same_time(hours.midnight, times.midnight, &date);
+}
+
+#[test]
+fn should_be_the_day_containing_the_julian_day() {
+    let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    let date = NaiveDate::from_ymd_opt(2024, 3, 20).unwrap();
+    let start = julian_from_datetime(&date.and_hms_opt(0, 0, 0).unwrap());
+
+    let morning = calculator.calculate_hours(&istanbul(), start + 0.1);
+    let evening = calculator.calculate_hours(&istanbul(), start + 0.9);
+
+    assert_eq!(morning, evening);
+    assert_ne!(
+        morning,
+        calculator.calculate_hours(&istanbul(), start - 0.1)
+    );
+}
+
+#[test]
+fn should_not_have_the_missing_events() {
+    let calculator = Calculator::new(
+        Parameters {
+            high_latitudes: HighLatsMethod::None,
+            ..methods::MUSLIM_WORLD_LEAGUE
+        },
+        Default::default(),
+    );
+    let oslo = Location {
+        latitude: 59.91,
+        longitude: 10.75,
+        ..Default::default()
+    };
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+
+    let hours = calculator
+        .calculate_hours(&oslo, julian_day(&date))
+        .unwrap();
+
+    assert_eq!(hours.fajr, None);
+    assert_eq!(hours.isha, None);
+    assert!(hours.sunrise.is_some());
+    assert!(hours.adjusted.is_empty());
+}
+
+#[test]
+fn should_not_calculate_out_of_the_dates() {
+    let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+
+    assert_eq!(calculator.calculate_hours(&istanbul(), 1e12), None);
+}
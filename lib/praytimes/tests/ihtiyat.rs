@@ -0,0 +1,86 @@
+use chrono::{Duration, NaiveDate};
+use praytimes::{
+    error::CalcError,
+    methods,
+    types::{Ihtiyat, Location, Parameters, PraytimeType, TuneOffsets},
+    Calculator,
+};
+
+fn istanbul() -> Location {
+    Location {
+        latitude: 41.01,
+        longitude: 28.98,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn should_add_the_ihtiyat_of_the_method() {
+    let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    let diyanet = Calculator::new(methods::DIYANET_ISLERI_BASKANLIGI, Default::default())
+        .calculate(&istanbul(), &date);
+    let without = Calculator::new(
+        Parameters {
+            ihtiyat: Ihtiyat::NONE,
+            ..methods::DIYANET_ISLERI_BASKANLIGI
+        },
+        Default::default(),
+    )
+    .calculate(&istanbul(), &date);
+
+    let minutes = |event: PraytimeType| {
+        let (time, base) = match event {
+            PraytimeType::Sunrise => (diyanet.sunrise, without.sunrise),
+            PraytimeType::Dhuhr => (diyanet.dhuhr, without.dhuhr),
+            PraytimeType::Asr => (diyanet.asr, without.asr),
+            PraytimeType::Maghrib => (diyanet.maghrib, without.maghrib),
+            _ => (diyanet.fajr, without.fajr),
+        };
+        (time.unwrap() - base.unwrap()).num_minutes()
+    };
+    assert_eq!(minutes(PraytimeType::Sunrise), -7);
+    assert_eq!(minutes(PraytimeType::Dhuhr), 5);
+    assert_eq!(minutes(PraytimeType::Asr), 4);
+    assert_eq!(minutes(PraytimeType::Maghrib), 7);
+    assert_eq!(minutes(PraytimeType::Fajr), 0);
+    assert_eq!(diyanet.sunset, without.sunset);
+}
+
+#[test]
+fn should_tune_on_top_of_the_ihtiyat() {
+    let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    let method = Calculator::new(methods::KEMENTERIAN_AGAMA_INDONESIA, Default::default())
+        .calculate(&istanbul(), &date);
+    let tuned = Calculator::new(
+        methods::KEMENTERIAN_AGAMA_INDONESIA,
+        TuneOffsets {
+            isha: Some(3.0),
+            ..Default::default()
+        },
+    )
+    .calculate(&istanbul(), &date);
+
+    assert_eq!(
+        tuned.isha.unwrap() - method.isha.unwrap(),
+        Duration::minutes(3)
+    );
+    assert_eq!(tuned.fajr, method.fajr);
+}
+
+#[test]
+fn should_reject_invalid_ihtiyat() {
+    let params = Parameters {
+        ihtiyat: Ihtiyat {
+            asr: f64::INFINITY,
+            ..Ihtiyat::NONE
+        },
+        ..methods::MUSLIM_WORLD_LEAGUE
+    };
+    assert_eq!(
+        params.validate(),
+        Err(CalcError::InvalidMinutes {
+            event: PraytimeType::Asr,
+            minutes: f64::INFINITY
+        })
+    );
+}
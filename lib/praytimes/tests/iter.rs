@@ -0,0 +1,137 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use praytimes::{
+    iter::PraytimesIterator,
+    types::{Location, PraytimeType},
+};
+
+mod common;
+use common::{calculator, date, tehran};
+
+fn iterator(start: NaiveDateTime) -> PraytimesIterator {
+    PraytimesIterator::new(calculator(), tehran(), start)
+}
+
+#[test]
+fn should_start_from_the_instant_in_order() {
+    let times = calculator().calculate(&tehran(), &date());
+    let next = calculator().calculate(&tehran(), &date().succ_opt().unwrap());
+
+    let events: Vec<_> = iterator(times.asr.unwrap()).take(7).collect();
+    assert_eq!(
+        events,
+        vec![
+            (PraytimeType::Asr, times.asr.unwrap()),
+            (PraytimeType::Sunset, times.sunset.unwrap()),
+            (PraytimeType::Maghrib, times.maghrib.unwrap()),
+            (PraytimeType::Isha, times.isha.unwrap()),
+            (PraytimeType::Midnight, times.midnight.unwrap()),
+            (PraytimeType::Imsak, next.imsak.unwrap()),
+            (PraytimeType::Fajr, next.fajr.unwrap()),
+        ]
+    );
+}
+
+#[test]
+fn should_stay_chronological_over_the_days() {
+    let start = date().and_hms_opt(0, 0, 0).unwrap();
+    let events: Vec<_> = iterator(start).take(9 * 40).collect();
+
+    assert_eq!(events.len(), 9 * 40);
+    assert!(events.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    assert!(events[0].1 >= start);
+}
+
+#[test]
+fn should_filter_the_types_and_stop_at_the_end() {
+    let start = date().and_hms_opt(0, 0, 0).unwrap();
+    let end = start + Duration::days(7);
+    let dhuhrs: Vec<_> = iterator(start)
+        .with_end(end)
+        .with_types([PraytimeType::Dhuhr])
+        .collect();
+
+    assert_eq!(dhuhrs.len(), 7);
+    assert!(dhuhrs
+        .iter()
+        .all(|(kind, time)| *kind == PraytimeType::Dhuhr && *time < end));
+}
+
+#[test]
+fn should_walk_backwards() {
+    let start = date().and_hms_opt(0, 0, 0).unwrap();
+    let end = start + Duration::days(3);
+    let forward: Vec<_> = iterator(start).with_end(end).collect();
+    let mut backward: Vec<_> = iterator(start).with_end(end).rev().collect();
+    backward.reverse();
+    assert_eq!(forward, backward);
+
+    // the two ends meet without repeating an event
+    let mut both = iterator(start).with_end(end);
+    let mut met = Vec::new();
+    while let Some(event) = both.next() {
+        met.push(event);
+        if let Some(event) = both.next_back() {
+            met.push(event);
+        }
+    }
+    met.sort_by_key(|(kind, time)| (*time, *kind));
+    assert_eq!(met, forward);
+}
+
+#[test]
+fn should_walk_to_the_previous_prayers_without_an_end() {
+    let times = calculator().calculate(&tehran(), &date());
+    let previous = calculator().calculate(&tehran(), &date().pred_opt().unwrap());
+    let mut prayers = iterator(times.dhuhr.unwrap());
+
+    assert_eq!(
+        prayers.next_back(),
+        Some((PraytimeType::Sunrise, times.sunrise.unwrap()))
+    );
+    assert_eq!(
+        prayers.next(),
+        Some((PraytimeType::Dhuhr, times.dhuhr.unwrap()))
+    );
+    let isha = prayers
+        .rev()
+        .find(|(kind, _)| *kind == PraytimeType::Isha)
+        .unwrap();
+    assert_eq!(isha.1, previous.isha.unwrap());
+}
+
+#[test]
+fn should_keep_the_events_overlapping_the_next_day() {
+    // the imsak of a day is before the isha of the day before in the summer of oslo
+    let oslo = Location {
+        latitude: 59.9,
+        longitude: 10.75,
+        ..Default::default()
+    };
+    let date = NaiveDate::from_ymd_opt(2024, 6, 18).unwrap();
+    let times = calculator().calculate(&oslo, &date);
+    let next = calculator().calculate(&oslo, &date.succ_opt().unwrap());
+    assert!(next.imsak.unwrap() < times.isha.unwrap());
+
+    let start = date.and_hms_opt(0, 0, 0).unwrap();
+    let end = start + Duration::days(7);
+    let events: Vec<_> = PraytimesIterator::new(calculator(), oslo.clone(), start)
+        .with_end(end)
+        .collect();
+    let mut expected: Vec<_> = (-1..=7)
+        .flat_map(|days| {
+            calculator()
+                .calculate(&oslo, &(date + Duration::days(days)))
+                .into_vec()
+        })
+        .filter(|(_, time)| start <= *time && *time < end)
+        .collect();
+    expected.sort_by_key(|(kind, time)| (*time, *kind));
+    assert_eq!(events, expected);
+
+    let mut backward: Vec<_> = PraytimesIterator::new(calculator(), oslo, start)
+        .with_end(end)
+        .rev()
+        .collect();
+    backward.reverse();
+    assert_eq!(backward, expected);
+}
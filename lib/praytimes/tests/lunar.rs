@@ -0,0 +1,84 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use praytimes::{
+    lunar::{self, OdehZone, YallopZone},
+    solar::Almanac,
+};
+
+mod common;
+use common::tehran;
+
+fn datetime(date: (i32, u32, u32), time: (u32, u32)) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(date.0, date.1, date.2)
+        .unwrap()
+        .and_hms_opt(time.0, time.1, 0)
+        .unwrap()
+}
+
+#[test]
+fn should_find_the_conjunction() {
+    // published new moons in utc
+    let cases = [
+        ((2023, 3, 21), (17, 23)),
+        ((2024, 3, 10), (9, 0)),
+        ((2024, 4, 8), (18, 21)),
+    ];
+    for (date, time) in cases {
+        let expected = datetime(date, time);
+        let conjunction = lunar::conjunction_near(&(expected + chrono::Duration::days(5))).unwrap();
+        let difference = (conjunction - expected).num_minutes().abs();
+        assert!(difference <= 3, "{expected}: {conjunction}");
+    }
+}
+
+#[test]
+fn should_not_see_the_moon_before_the_conjunction() {
+    let date = NaiveDate::from_ymd_opt(2023, 3, 21).unwrap();
+    let report = lunar::crescent_visibility(&tehran(), &date, &Almanac).unwrap();
+
+    assert!(report.age < 0.0);
+    assert!(report.lag < 0.0);
+    assert!(report.moonset < report.sunset);
+    assert_eq!(report.yallop.zone, YallopZone::F);
+    assert_eq!(report.odeh.zone, OdehZone::D);
+}
+
+#[test]
+fn should_see_the_crescent_more_easily_each_evening() {
+    let reports: Vec<_> = [22, 23]
+        .into_iter()
+        .map(|day| {
+            let date = NaiveDate::from_ymd_opt(2023, 3, day).unwrap();
+            lunar::crescent_visibility(&tehran(), &date, &Almanac).unwrap()
+        })
+        .collect();
+
+    assert_eq!(reports[0].yallop.zone, YallopZone::B);
+    assert_eq!(reports[0].odeh.zone, OdehZone::B);
+    assert!((reports[0].lag - 53.0).abs() < 2.0, "{}", reports[0].lag);
+    assert!(reports[0].best_time > reports[0].sunset);
+    assert!(reports[0].best_time < reports[0].moonset);
+
+    assert_eq!(reports[1].yallop.zone, YallopZone::A);
+    assert_eq!(reports[1].odeh.zone, OdehZone::A);
+    assert!(reports[1].elongation > reports[0].elongation);
+    assert!(reports[1].width > reports[0].width);
+}
+
+#[test]
+fn should_classify_the_zones() {
+    let yallop: Vec<_> = [0.3, 0.0, -0.1, -0.2, -0.25, -0.3]
+        .into_iter()
+        .map(YallopZone::from_q)
+        .collect();
+    use YallopZone::*;
+    assert_eq!(yallop, vec![A, B, C, D, E, F]);
+
+    let odeh: Vec<_> = [5.65, 2.0, -0.96, -1.0]
+        .into_iter()
+        .map(OdehZone::from_v)
+        .collect();
+    assert_eq!(
+        odeh,
+        vec![OdehZone::A, OdehZone::B, OdehZone::C, OdehZone::D]
+    );
+}
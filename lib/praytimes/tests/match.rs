@@ -1,6 +1,8 @@
+use std::collections::BTreeMap;
+
 use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
 use praytimes::{
-    types::{Location, Parameters, PraytimesOutput, TuneOffsets},
+    types::{Location, Parameters, PraytimeType, PraytimesOutput, Refinement, TuneOffsets},
     Calculator,
 };
 use serde::{ser::SerializeSeq, Deserialize, Deserializer, Serialize, Serializer};
@@ -39,6 +41,7 @@ where
         .ok_or(serde::de::Error::custom("Invalid date"))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn assert_prayertime(
     name: &str,
     real: Option<NaiveDateTime>,
@@ -175,10 +178,70 @@ fn should_match_with_the_main() {
     }
 }
 
+#[test]
+fn should_converge_close_to_the_main() {
+    let refinement = Refinement::Converge {
+        tolerance: 1.0,
+        max_iterations: 10,
+    };
+    for TestCase {
+        inputs: Inputs {
+            params,
+            location,
+            date,
+        },
+        expected_output,
+    } in get_data()
+    {
+        let calculator = Calculator::new(params.clone(), Default::default());
+        let output = calculator.calculate(&location, &date);
+        let refined = calculator
+            .with_refinement(refinement)
+            .calculate(&location, &date);
+        let converged = Calculator::new(params.clone(), Default::default())
+            .with_refinement(Refinement::Iterations(10))
+            .calculate(&location, &date);
+
+        // one computation with fixed guesses is off by up to a few minutes in high latitudes
+        for (name, time, expected) in pair_by_type(&refined, &output) {
+            let drift = (time - expected).num_seconds().abs();
+            assert!(
+                drift < 300,
+                "{name:?} drifted {drift}s\nlocation: {location:?}\ndate: {date}\nexpected: {expected_output:?}"
+            );
+        }
+        for (name, time, expected) in pair_by_type(&refined, &converged) {
+            assert!(
+                (time - expected).num_seconds().abs() <= 1,
+                "{name:?} didn't converge\nlocation: {location:?}\ndate: {date}"
+            );
+        }
+    }
+}
+
+/// the times of the same events in two outputs, an event can't exist in only one of them
+fn pair_by_type(
+    output: &PraytimesOutput,
+    other: &PraytimesOutput,
+) -> Vec<(PraytimeType, NaiveDateTime, NaiveDateTime)> {
+    let times = output.into_vec().into_iter().collect::<BTreeMap<_, _>>();
+    let other_times = other.into_vec().into_iter().collect::<BTreeMap<_, _>>();
+    assert_eq!(
+        times.keys().collect::<Vec<_>>(),
+        other_times.keys().collect::<Vec<_>>(),
+        "outputs have different events\n{output:#?}\n{other:#?}"
+    );
+    times
+        .into_iter()
+        .map(|(name, time)| (name, time, other_times[&name]))
+        .collect()
+}
+
+/// generated by ./assets/generate-test-data.js with PrayTimes.js, the file it ran is in
+/// ./assets/test-data.source.json
 fn get_data() -> Vec<TestCase> {
     let data = std::fs::read_to_string("./assets/test-data.json").unwrap();
-    let data = serde_json::from_str::<Vec<TestCase>>(&data).unwrap();
-    data
+    serde_json::from_str::<Vec<TestCase>>(&data).unwrap()
 }
 
 #[test]
@@ -204,4 +267,3 @@ fn should_tune_successfully() {
         data.expected_output.dhuhr.unwrap().second()
     )
 }
-
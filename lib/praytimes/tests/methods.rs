@@ -0,0 +1,143 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use praytimes::{
+    methods::{self, Method, MethodRegistry},
+    types::{AsrFactor, Ihtiyat, Location, Parameters, Rounding, RoundingPolicy},
+    Calculator,
+};
+
+#[test]
+fn should_find_the_methods_by_name_and_alias() {
+    for (name, alias) in [
+        ("GULF_REGION", "Gulf"),
+        ("DIYANET_ISLERI_BASKANLIGI", "Turkey"),
+        ("MAJLIS_UGAMA_ISLAM_SINGAPURA", "Singapore"),
+        ("MINISTRY_OF_RELIGIOUS_AFFAIRS_ALGERIA", "Algeria"),
+    ] {
+        let method = methods::get_method_by_name(name);
+        assert!(method.is_some(), "{name}");
+        assert_eq!(method, methods::get_method_by_name(alias));
+    }
+    assert_eq!(methods::get_method_by_name("Kuwait"), Some(methods::KUWAIT));
+    assert_eq!(methods::get_method_by_name("Atlantis"), None);
+}
+
+#[test]
+fn should_name_each_method_once() {
+    let registry = MethodRegistry::default();
+    let mut names: Vec<_> = registry
+        .iter()
+        .flat_map(|method| std::iter::once(&method.id).chain(&method.aliases))
+        .map(|name| name.to_ascii_lowercase())
+        .collect();
+    let count = names.len();
+    names.sort();
+    names.dedup();
+    assert_eq!(names.len(), count);
+}
+
+#[test]
+fn should_apply_the_fixed_offsets_of_the_method() {
+    let dubai = Location {
+        latitude: 25.2,
+        longitude: 55.27,
+        ..Default::default()
+    };
+    let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    let times = Calculator::new(methods::DUBAI, Default::default()).calculate(&dubai, &date);
+    let without_offsets = Calculator::new(
+        Parameters {
+            dhuhr: methods::MUSLIM_WORLD_LEAGUE.dhuhr,
+            maghrib: methods::MUSLIM_WORLD_LEAGUE.maghrib,
+            ihtiyat: Ihtiyat::NONE,
+            ..methods::DUBAI
+        },
+        Default::default(),
+    )
+    .calculate(&dubai, &date);
+
+    let minutes = |later: Option<NaiveDateTime>, earlier: Option<NaiveDateTime>| {
+        (later.unwrap() - earlier.unwrap()).num_seconds() as f64 / 60.0
+    };
+    assert!((minutes(times.dhuhr, without_offsets.dhuhr) - 3.0).abs() < 0.01);
+    assert!((minutes(times.maghrib, without_offsets.maghrib) - 3.0).abs() < 0.01);
+    assert!((minutes(times.sunrise, without_offsets.sunrise) + 3.0).abs() < 0.01);
+    assert!((minutes(times.asr, without_offsets.asr) - 3.0).abs() < 0.01);
+    assert_eq!(times.fajr, without_offsets.fajr);
+}
+
+#[test]
+fn should_round_like_the_published_tables() {
+    let singapore = Location {
+        latitude: 1.35,
+        longitude: 103.82,
+        ..Default::default()
+    };
+    let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    for (method, rounding) in [
+        (methods::MAJLIS_UGAMA_ISLAM_SINGAPURA, Rounding::Ceil),
+        (methods::JABATAN_KEMAJUAN_ISLAM_MALAYSIA, Rounding::Ceil),
+        (methods::DIYANET_ISLERI_BASKANLIGI, Rounding::Nearest),
+    ] {
+        let calculator = Calculator::new(method, Default::default());
+        let times = calculator.calculate(&singapore, &date);
+        let exact = calculator
+            .with_rounding(RoundingPolicy::NONE)
+            .calculate(&singapore, &date);
+
+        let rounded = exact
+            .into_vec()
+            .into_iter()
+            .map(|(event, time)| (event, rounding.round(time)))
+            .collect::<Vec<_>>();
+        assert_eq!(times.into_vec(), rounded);
+    }
+}
+
+#[test]
+fn should_register_methods_at_runtime() {
+    let mut registry = MethodRegistry::default();
+    let hanafi_mwl = Parameters {
+        asr: AsrFactor { factor: 2.0 },
+        ..methods::MUSLIM_WORLD_LEAGUE
+    };
+    let method = Method {
+        id: "HANAFI_MWL".into(),
+        name: "Hanafi Muslim World League".into(),
+        aliases: vec!["MWL".into()],
+        region: "Europe".into(),
+        authority: "Local mosque".into(),
+        parameters: hanafi_mwl.clone(),
+    };
+    assert_eq!(registry.register(method.clone()), None);
+
+    // ids win over aliases, and later registrations over earlier aliases
+    assert_eq!(registry.get("hanafi_mwl"), Some(&method));
+    assert_eq!(registry.get("MWL"), Some(&method));
+    assert_eq!(
+        registry.get("MUSLIM_WORLD_LEAGUE").unwrap().parameters,
+        methods::MUSLIM_WORLD_LEAGUE
+    );
+
+    let renamed = Method {
+        name: "Hanafi MWL".into(),
+        ..method.clone()
+    };
+    assert_eq!(registry.register(renamed), Some(method));
+    assert_eq!(registry.iter().filter(|m| m.id == "HANAFI_MWL").count(), 1);
+}
+
+#[test]
+fn should_find_the_method_of_parameters() {
+    let registry = MethodRegistry::default();
+    assert_eq!(
+        registry.find_by_parameters(&methods::DUBAI).unwrap().id,
+        "DUBAI"
+    );
+
+    let custom = Parameters {
+        asr: AsrFactor { factor: 2.0 },
+        ..methods::DUBAI
+    };
+    assert_eq!(registry.find_by_parameters(&custom), None);
+    assert!(MethodRegistry::empty().iter().next().is_none());
+}
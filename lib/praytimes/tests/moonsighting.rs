@@ -0,0 +1,79 @@
+use chrono::{Duration, NaiveDate};
+use praytimes::{
+    methods,
+    types::{Location, PraytimeType, Shafaq},
+    Calculator,
+};
+
+fn location(latitude: f64, longitude: f64) -> Location {
+    Location {
+        latitude,
+        longitude,
+        ..Default::default()
+    }
+}
+
+fn minutes(duration: Duration) -> f64 {
+    duration.num_seconds() as f64 / 60.0
+}
+
+#[test]
+fn should_limit_the_twilight_to_the_seasonal_minutes() {
+    let london = location(51.5, -0.13);
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+    let calculator = Calculator::new(
+        methods::MOONSIGHTING_COMMITTEE_WORLDWIDE,
+        Default::default(),
+    );
+    let times = calculator.calculate(&london, &date);
+
+    let fajr = minutes(times.sunrise.unwrap() - times.fajr.unwrap());
+    assert!((fajr - 119.7).abs() < 0.5, "{fajr}");
+    let isha = minutes(times.isha.unwrap() - times.sunset.unwrap());
+    assert!((isha - 80.4).abs() < 0.5, "{isha}");
+    let maghrib = minutes(times.maghrib.unwrap() - times.sunset.unwrap());
+    assert!((maghrib - 3.0).abs() < 0.1, "{maghrib}");
+    assert_eq!(
+        times.adjusted,
+        vec![PraytimeType::Imsak, PraytimeType::Fajr, PraytimeType::Isha]
+    );
+}
+
+#[test]
+fn should_use_the_isha_of_the_shafaq() {
+    let london = location(51.5, -0.13);
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+    let isha = |shafaq| {
+        let params = methods::MOONSIGHTING_COMMITTEE_WORLDWIDE.with_shafaq(shafaq);
+        let times = Calculator::new(params, Default::default()).calculate(&london, &date);
+        minutes(times.isha.unwrap() - times.sunset.unwrap())
+    };
+
+    let ahmar = isha(Shafaq::Ahmar);
+    let general = isha(Shafaq::General);
+    let abyad = isha(Shafaq::Abyad);
+    assert!((ahmar - 79.9).abs() < 0.5, "{ahmar}");
+    assert!((abyad - 150.7).abs() < 0.5, "{abyad}");
+    assert!(ahmar < general && general < abyad);
+}
+
+#[test]
+fn should_keep_the_fixed_angle_within_the_seasonal_minutes() {
+    let tehran = location(35.7, 51.4);
+    let date = NaiveDate::from_ymd_opt(2023, 1, 15).unwrap();
+    let seasonal = Calculator::new(
+        methods::MOONSIGHTING_COMMITTEE_WORLDWIDE,
+        Default::default(),
+    )
+    .calculate(&tehran, &date);
+    let fixed = Calculator::new(
+        methods::UNIVERSITY_OF_ISLAMIC_SCIENCES_KARACHI,
+        Default::default(),
+    )
+    .calculate(&tehran, &date);
+
+    assert_eq!(seasonal.fajr, fixed.fajr);
+    // 88 minutes after sunset at 18°, the limit is 87.4 minutes in mid january
+    assert!(seasonal.isha < fixed.isha);
+    assert_eq!(seasonal.adjusted, vec![PraytimeType::Isha]);
+}
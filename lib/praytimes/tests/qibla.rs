@@ -0,0 +1,78 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+use praytimes::{
+    qibla::{self, Qibla, KAABA},
+    solar::{Almanac, Vsop87},
+    types::Location,
+};
+
+fn location(latitude: f64, longitude: f64) -> Location {
+    Location {
+        latitude,
+        longitude,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn should_find_the_direction_and_distance() {
+    // (latitude, longitude, direction, distance in km)
+    let cases = [
+        (40.7128, -74.0060, 58.48, 10_300.0), // New York
+        (35.6892, 51.3890, 218.4, 1_950.0),   // Tehran
+        (-6.2088, 106.8456, 295.15, 7_920.0), // Jakarta
+        (51.5074, -0.1278, 118.99, 4_790.0),  // London
+    ];
+
+    for (latitude, longitude, direction, distance) in cases {
+        let qibla = Qibla::new(&location(latitude, longitude));
+        assert!(
+            (qibla.direction - direction).abs() < 0.5,
+            "{latitude}, {longitude}: {qibla:?}"
+        );
+        assert!(
+            (qibla.distance - distance).abs() / distance < 0.01,
+            "{latitude}, {longitude}: {qibla:?}"
+        );
+    }
+
+    assert!(Qibla::new(&KAABA).distance < 1e-6);
+}
+
+#[test]
+fn should_find_when_the_sun_is_over_the_kaaba() {
+    let transits = qibla::sun_over_kaaba(2024, &Almanac);
+    let dates: Vec<(u32, u32, bool)> = transits
+        .iter()
+        .map(|t| (t.instant.month(), t.instant.day(), t.antipode))
+        .collect();
+
+    assert_eq!(
+        dates,
+        vec![
+            (1, 13, true),
+            (5, 27, false),
+            (7, 15, false),
+            (11, 28, true)
+        ]
+    );
+
+    // around 12:18 and 12:27 in Makkah ( UTC+3 )
+    let minutes = |t: NaiveDateTime| t.hour() * 60 + t.minute();
+    assert!(minutes(transits[1].instant).abs_diff(9 * 60 + 18) <= 1);
+    assert!(minutes(transits[2].instant).abs_diff(9 * 60 + 27) <= 1);
+}
+
+#[test]
+fn should_agree_between_solar_models() {
+    let almanac = qibla::sun_over_kaaba(2023, &Almanac);
+    let vsop87 = qibla::sun_over_kaaba(2023, &Vsop87);
+    assert_eq!(almanac.len(), vsop87.len());
+
+    for (a, b) in almanac.iter().zip(vsop87) {
+        assert_eq!(a.antipode, b.antipode);
+        assert!((a.instant - b.instant).num_seconds().abs() < 60);
+    }
+
+    let first = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+    assert!(almanac.iter().all(|t| t.instant.date() >= first));
+}
@@ -0,0 +1,108 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use praytimes::{
+    methods,
+    solar::{Almanac, SolarModel},
+    types::{Location, Refinement},
+    Calculator,
+};
+
+fn oslo() -> Location {
+    Location {
+        latitude: 59.91,
+        longitude: 10.75,
+        ..Default::default()
+    }
+}
+
+fn julian_date(time: &NaiveDateTime) -> f64 {
+    2440587.5 + time.timestamp_millis() as f64 / 86_400_000.0
+}
+
+/// altitude of the sun in degrees at an instant
+fn altitude(location: &Location, time: &NaiveDateTime) -> f64 {
+    let jd = julian_date(time);
+    let position = Almanac.sun_position(jd);
+    let hours = (jd + 0.5).fract() * 24.0;
+    let hour_angle =
+        (15.0 * (hours + location.longitude / 15.0 + position.equation - 12.0)).to_radians();
+    let (latitude, declination) = (
+        location.latitude.to_radians(),
+        position.declination.to_radians(),
+    );
+
+    (latitude.sin() * declination.sin() + latitude.cos() * declination.cos() * hour_angle.cos())
+        .asin()
+        .to_degrees()
+}
+
+#[test]
+fn should_put_the_sun_at_the_horizon_after_refining() {
+    let location = oslo();
+    let date = NaiveDate::from_ymd_opt(2023, 6, 21).unwrap();
+    let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    let refined = calculator
+        .clone()
+        .with_refinement(Refinement::Iterations(3))
+        .calculate(&location, &date);
+    let unrefined = calculator.calculate(&location, &date);
+
+    for (name, refined, unrefined) in [
+        ("sunrise", refined.sunrise, unrefined.sunrise),
+        ("sunset", refined.sunset, unrefined.sunset),
+    ] {
+        let refined = altitude(&location, &refined.unwrap()) + 0.833;
+        let unrefined = altitude(&location, &unrefined.unwrap()) + 0.833;
+        assert!(refined.abs() < unrefined.abs(), "{name}");
+        assert!(refined.abs() < 1e-4, "{name}: {refined}");
+    }
+}
+
+#[test]
+fn should_converge() {
+    let location = oslo();
+    let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+
+    for date in NaiveDate::from_ymd_opt(2023, 1, 1)
+        .unwrap()
+        .iter_days()
+        .step_by(30)
+        .take(13)
+    {
+        let converged = calculator
+            .clone()
+            .with_refinement(Refinement::Converge {
+                tolerance: 0.1,
+                max_iterations: 10,
+            })
+            .calculate(&location, &date);
+        let iterated = calculator
+            .clone()
+            .with_refinement(Refinement::Iterations(10))
+            .calculate(&location, &date);
+
+        for (converged, iterated) in converged.into_vec().iter().zip(iterated.into_vec()) {
+            assert_eq!(converged.0, iterated.0);
+            let difference = (converged.1 - iterated.1).num_milliseconds();
+            assert!(
+                difference.abs() <= 1000,
+                "{date} {}: {difference}ms",
+                converged.0
+            );
+        }
+    }
+}
+
+#[test]
+fn should_not_change_anything_by_default() {
+    let location = oslo();
+    let date = NaiveDate::from_ymd_opt(2023, 12, 21).unwrap();
+    let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+
+    assert_eq!(
+        calculator.calculate(&location, &date),
+        calculator
+            .clone()
+            .with_refinement(Refinement::Iterations(0))
+            .calculate(&location, &date)
+    );
+}
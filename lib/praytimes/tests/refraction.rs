@@ -0,0 +1,95 @@
+use chrono::NaiveDate;
+use praytimes::{
+    methods,
+    types::{Atmosphere, Location},
+    Calculator,
+};
+
+mod common;
+use common::calculator;
+
+fn location(latitude: f64, longitude: f64, atmosphere: Option<Atmosphere>) -> Location {
+    Location {
+        latitude,
+        longitude,
+        elevation: 0.0,
+        atmosphere,
+    }
+}
+
+#[test]
+fn should_be_the_default_in_a_standard_atmosphere() {
+    let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    let default = calculator().calculate(&location(35.7, 51.4, None), &date);
+    let standard = calculator().calculate(&location(35.7, 51.4, Some(Default::default())), &date);
+
+    for (default, standard) in [
+        (default.sunrise, standard.sunrise),
+        (default.sunset, standard.sunset),
+    ] {
+        let difference = (default.unwrap() - standard.unwrap()).num_milliseconds();
+        assert!(difference.abs() <= 1, "{difference}ms");
+    }
+    assert_eq!(default.fajr, standard.fajr);
+    assert_eq!(default.isha, standard.isha);
+}
+
+#[test]
+fn should_not_refract_the_twilight_angles() {
+    let date = NaiveDate::from_ymd_opt(2023, 7, 15).unwrap();
+    let hot = Atmosphere {
+        temperature: 48.0,
+        pressure: 995.0,
+    };
+    // maghrib at 4.5° below the horizon
+    let calculator = Calculator::new(
+        methods::INSTITUTE_OF_GEOPHYSICS_UNIVERSITY_OF_TEHRAN,
+        Default::default(),
+    );
+    let default = calculator.calculate(&location(35.7, 51.4, None), &date);
+    let desert = calculator.calculate(&location(35.7, 51.4, Some(hot)), &date);
+
+    assert_ne!(desert.sunset, default.sunset);
+    assert_eq!(desert.fajr, default.fajr);
+    assert_eq!(desert.maghrib, default.maghrib);
+    assert_eq!(desert.isha, default.isha);
+}
+
+#[test]
+fn should_shorten_the_day_in_hot_deserts() {
+    let date = NaiveDate::from_ymd_opt(2023, 7, 15).unwrap();
+    let hot = Atmosphere {
+        temperature: 48.0,
+        pressure: 995.0,
+    };
+    let default = calculator().calculate(&location(24.5, 54.4, None), &date);
+    let desert = calculator().calculate(&location(24.5, 54.4, Some(hot)), &date);
+
+    assert!(desert.sunrise > default.sunrise);
+    assert!(desert.sunset < default.sunset);
+    assert_eq!(desert.dhuhr, default.dhuhr);
+}
+
+#[test]
+fn should_lengthen_the_day_in_cold_cities() {
+    let date = NaiveDate::from_ymd_opt(2023, 1, 15).unwrap();
+    let cold = Atmosphere {
+        temperature: -30.0,
+        pressure: 1035.0,
+    };
+    let default = calculator().calculate(&location(62.0, 129.7, None), &date);
+    let winter = calculator().calculate(&location(62.0, 129.7, Some(cold)), &date);
+
+    assert!(winter.sunrise < default.sunrise);
+    assert!(winter.sunset > default.sunset);
+}
+
+#[test]
+fn should_refract_only_above_the_horizon() {
+    let atmosphere = Atmosphere::default();
+
+    assert!((atmosphere.refraction(0.0) - (0.833 - 0.2667)).abs() < 1e-9);
+    assert!(atmosphere.refraction(45.0) < 0.02);
+    assert_eq!(atmosphere.refraction(-4.0), 0.0);
+    assert_eq!(atmosphere.refraction(-18.0), 0.0);
+}
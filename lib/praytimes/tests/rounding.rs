@@ -0,0 +1,163 @@
+use chrono::{FixedOffset, NaiveDate, NaiveDateTime, Timelike};
+use praytimes::{
+    methods,
+    types::{Location, Parameters, Rounding, RoundingPolicy, TuneOffsets},
+    Calculator,
+};
+
+fn time(hour: u32, min: u32, sec: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2023, 6, 1)
+        .unwrap()
+        .and_hms_opt(hour, min, sec)
+        .unwrap()
+}
+
+#[test]
+fn should_round_times() {
+    let t = time(4, 12, 31);
+    assert_eq!(Rounding::None.round(t), t);
+    assert_eq!(Rounding::Nearest.round(t), time(4, 13, 0));
+    assert_eq!(Rounding::Nearest.round(time(4, 12, 29)), time(4, 12, 0));
+    assert_eq!(Rounding::Ceil.round(t), time(4, 13, 0));
+    assert_eq!(Rounding::Ceil.round(time(4, 12, 0)), time(4, 12, 0));
+    assert_eq!(Rounding::Floor.round(t), time(4, 12, 0));
+    assert_eq!(Rounding::Minutes(5).round(t), time(4, 15, 0));
+    assert_eq!(Rounding::Minutes(5).round(time(4, 12, 29)), time(4, 10, 0));
+    assert_eq!(
+        Rounding::CeilMinutes(5).round(time(4, 10, 1)),
+        time(4, 15, 0)
+    );
+    assert_eq!(
+        Rounding::CeilMinutes(5).round(time(4, 10, 0)),
+        time(4, 10, 0)
+    );
+    assert_eq!(
+        Rounding::FloorMinutes(5).round(time(4, 14, 59)),
+        time(4, 10, 0)
+    );
+    assert_eq!(Rounding::FloorMinutes(0).round(t), t);
+}
+
+#[test]
+fn should_round_on_the_local_minutes() {
+    // Kathmandu, 22:24 utc is 04:09 on the local clock
+    let kathmandu = FixedOffset::east_opt(5 * 3600 + 45 * 60).unwrap();
+    let t = time(22, 24, 0);
+    assert_eq!(Rounding::Minutes(10).round(t), time(22, 20, 0));
+    assert_eq!(
+        Rounding::Minutes(10).round_in(t, kathmandu),
+        time(22, 25, 0)
+    );
+    assert_eq!(
+        Rounding::FloorMinutes(30).round_in(t, kathmandu),
+        time(22, 15, 0)
+    );
+    assert_eq!(
+        Rounding::CeilMinutes(15).round_in(t, kathmandu),
+        time(22, 30, 0)
+    );
+    // 04:00 on the local clock
+    assert_eq!(
+        Rounding::CeilMinutes(15).round_in(time(22, 15, 0), kathmandu),
+        time(22, 15, 0)
+    );
+    // a whole minute in utc is a whole minute in any offset
+    assert_eq!(
+        Rounding::Nearest.round_in(time(22, 24, 31), kathmandu),
+        time(22, 25, 0)
+    );
+}
+
+#[test]
+fn should_round_after_tuning() {
+    let location = Location {
+        latitude: 35.7,
+        longitude: 51.4,
+        ..Default::default()
+    };
+    let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    let tune = TuneOffsets {
+        fajr: Some(0.5),
+        ..Default::default()
+    };
+    let tuned = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, tune.clone());
+    let rounded = tuned.clone().with_rounding(RoundingPolicy {
+        fajr: Rounding::Ceil,
+        isha: Rounding::Floor,
+        ..Default::default()
+    });
+
+    let expected = tuned.calculate(&location, &date);
+    let times = rounded.calculate(&location, &date);
+    assert_eq!(times.fajr, expected.fajr.map(|t| Rounding::Ceil.round(t)));
+    assert_eq!(times.isha, expected.isha.map(|t| Rounding::Floor.round(t)));
+    assert_eq!(times.isha.unwrap().second(), 0);
+    assert_eq!(times.dhuhr, expected.dhuhr);
+
+    let all = tuned.with_rounding(RoundingPolicy::all(Rounding::Nearest));
+    let times = all.calculate(&location, &date);
+    for time in [times.imsak, times.sunrise, times.asr, times.midnight] {
+        assert_eq!(time.unwrap().second(), 0);
+    }
+}
+
+#[test]
+fn should_round_with_the_parameters() {
+    let location = Location {
+        latitude: 35.7,
+        longitude: 51.4,
+        ..Default::default()
+    };
+    let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    let rounding = RoundingPolicy {
+        asr: Rounding::CeilMinutes(5),
+        ..RoundingPolicy::all(Rounding::Floor)
+    };
+    let params = Parameters {
+        rounding: rounding.clone(),
+        ..methods::MUSLIM_WORLD_LEAGUE
+    };
+
+    let times = Calculator::new(params.clone(), Default::default()).calculate(&location, &date);
+    let expected = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default())
+        .with_rounding(rounding)
+        .calculate(&location, &date);
+    assert_eq!(times, expected);
+    assert_eq!(times.asr.unwrap().minute() % 5, 0);
+    assert_eq!(times.fajr.unwrap().second(), 0);
+
+    // the calculator replaces the rounding of the parameters
+    let unrounded = Calculator::new(params, Default::default())
+        .with_rounding(RoundingPolicy::NONE)
+        .calculate(&location, &date);
+    assert_eq!(
+        unrounded,
+        Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default())
+            .calculate(&location, &date)
+    );
+}
+
+#[test]
+fn should_round_on_the_minutes_of_the_utc_offset() {
+    let kathmandu = Location {
+        latitude: 27.7,
+        longitude: 85.3,
+        ..Default::default()
+    };
+    let offset = FixedOffset::east_opt(5 * 3600 + 45 * 60).unwrap();
+    let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default())
+        .with_rounding(RoundingPolicy::all(Rounding::Minutes(10)));
+
+    let utc = calculator.calculate(&kathmandu, &date);
+    let times = calculator
+        .with_utc_offset(offset)
+        .calculate(&kathmandu, &date);
+    for (utc, time) in utc.into_vec().into_iter().zip(times.into_vec()) {
+        let local = time.1 + offset;
+        assert_eq!(local.minute() % 10, 0, "{local}");
+        assert_eq!(local.second(), 0, "{local}");
+        // 5 minutes away from the utc rounding, +5:45 isn't a multiple of 10 minutes
+        assert_eq!((time.1 - utc.1).num_minutes().abs(), 5, "{local}");
+    }
+}
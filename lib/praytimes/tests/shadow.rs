@@ -0,0 +1,65 @@
+use chrono::NaiveDate;
+use praytimes::{
+    methods,
+    types::{AsrFactor, NoonSide, Parameters, Refinement},
+    Calculator,
+};
+
+mod common;
+use common::tehran;
+
+#[test]
+fn should_match_the_asr_of_the_parameters() {
+    let date = NaiveDate::from_ymd_opt(2023, 12, 1).unwrap();
+    for factor in [1.0, 2.0] {
+        let calculator = Calculator::new(
+            Parameters {
+                asr: AsrFactor { factor },
+                ..methods::MUSLIM_WORLD_LEAGUE
+            },
+            Default::default(),
+        );
+        assert_eq!(
+            calculator.shadow_time(&tehran(), &date, factor, NoonSide::AfterNoon),
+            calculator.calculate(&tehran(), &date).asr
+        );
+    }
+}
+
+#[test]
+fn should_be_symmetric_around_the_noon() {
+    let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default())
+        .with_refinement(Refinement::Iterations(2));
+    let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    let noon = calculator.calculate(&tehran(), &date).dhuhr.unwrap();
+
+    for factor in [0.5, 1.0, 2.0, 4.0] {
+        let before = calculator
+            .shadow_time(&tehran(), &date, factor, NoonSide::BeforeNoon)
+            .unwrap();
+        let after = calculator
+            .shadow_time(&tehran(), &date, factor, NoonSide::AfterNoon)
+            .unwrap();
+
+        assert!(before < noon && noon < after);
+        let asymmetry = ((noon - before) - (after - noon)).num_seconds();
+        assert!(asymmetry.abs() < 30, "{factor}: {asymmetry}s");
+    }
+}
+
+#[test]
+fn should_lengthen_the_shadow_through_the_afternoon() {
+    let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    let date = NaiveDate::from_ymd_opt(2023, 3, 1).unwrap();
+    let times: Vec<_> = [1.0, 2.0, 3.0, 7.0]
+        .into_iter()
+        .map(|factor| {
+            calculator
+                .shadow_time(&tehran(), &date, factor, NoonSide::AfterNoon)
+                .unwrap()
+        })
+        .collect();
+
+    assert!(times.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(times[3] < calculator.calculate(&tehran(), &date).sunset.unwrap());
+}
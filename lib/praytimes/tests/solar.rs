@@ -0,0 +1,134 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use praytimes::{
+    methods,
+    solar::{Almanac, SolarModel, Vsop87},
+    types::{Atmosphere, Location},
+    Calculator,
+};
+
+fn julian_date(datetime: NaiveDateTime) -> f64 {
+    2440587.5 + datetime.timestamp_millis() as f64 / 86_400_000.0
+}
+
+#[test]
+fn should_match_the_spa_reference_example() {
+    // example of the NREL SPA report, 2003-10-17 19:30:30 UTC
+    let datetime = NaiveDate::from_ymd_opt(2003, 10, 17)
+        .unwrap()
+        .and_hms_opt(19, 30, 30)
+        .unwrap();
+    let position = Vsop87.sun_position(julian_date(datetime));
+
+    assert!((position.declination - -9.31434).abs() < 0.0005);
+    assert!((position.equation * 60.0 - 14.641503).abs() < 0.02);
+}
+
+#[test]
+fn should_agree_with_the_almanac_over_decades() {
+    let mut datetime = NaiveDate::from_ymd_opt(1970, 1, 1)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap();
+    let end = NaiveDate::from_ymd_opt(2040, 1, 1)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap();
+
+    let (mut max_declination, mut max_equation) = (0.0_f64, 0.0_f64);
+    while datetime < end {
+        let jd = julian_date(datetime);
+        let almanac = Almanac.sun_position(jd);
+        let vsop87 = Vsop87.sun_position(jd);
+
+        max_declination = max_declination.max((almanac.declination - vsop87.declination).abs());
+        // the almanac's equation of time may be off by a whole day
+        let equation = (almanac.equation - vsop87.equation + 12.0).rem_euclid(24.0) - 12.0;
+        max_equation = max_equation.max(equation.abs() * 3600.0);
+        datetime += Duration::days(5);
+    }
+
+    // the almanac is accurate to about an arc minute
+    assert!(
+        max_declination < 1.0 / 60.0,
+        "declination {max_declination}"
+    );
+    assert!(max_equation < 5.0, "equation of time {max_equation}s");
+}
+
+#[test]
+fn should_calculate_with_the_selected_model() {
+    let location = Location {
+        latitude: 51.5,
+        longitude: -0.13,
+        elevation: 0.0,
+        atmosphere: None,
+    };
+    let almanac = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    let vsop87 = almanac.clone().with_solar_model(Vsop87);
+
+    let mut date = NaiveDate::from_ymd_opt(1990, 1, 1).unwrap();
+    let mut max = 0i64;
+    while date < NaiveDate::from_ymd_opt(2030, 1, 1).unwrap() {
+        let a = almanac.calculate(&location, &date);
+        let b = vsop87.calculate(&location, &date);
+        for (x, y) in [
+            (a.sunrise, b.sunrise),
+            (a.dhuhr, b.dhuhr),
+            (a.sunset, b.sunset),
+        ] {
+            max = max.max((x.unwrap() - y.unwrap()).num_seconds().abs());
+        }
+        date += Duration::days(7);
+    }
+    assert!(max < 10, "{max}s");
+}
+
+#[test]
+fn should_find_the_coordinates_of_the_spa_reference_example() {
+    let datetime = NaiveDate::from_ymd_opt(2003, 10, 17)
+        .unwrap()
+        .and_hms_opt(19, 30, 30)
+        .unwrap();
+    let golden = Location {
+        latitude: 39.742476,
+        longitude: -105.1786,
+        elevation: 1830.14,
+        atmosphere: Some(Atmosphere {
+            temperature: 11.0,
+            pressure: 820.0,
+        }),
+    };
+    let coordinates = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default())
+        .with_solar_model(Vsop87)
+        .sun_coordinates(&golden, &datetime);
+
+    // zenith 50.11162°, the topocentric parallax of the sun ( 0.002° ) is ignored
+    assert!((coordinates.altitude - (90.0 - 50.11162)).abs() < 0.005);
+    assert!((coordinates.azimuth - 194.34024).abs() < 0.005);
+    assert!((coordinates.hour_angle - 11.105900).abs() < 0.005);
+    assert!((coordinates.declination - -9.31434).abs() < 0.0005);
+}
+
+#[test]
+fn should_be_consistent_with_the_calculated_times() {
+    let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    let tehran = Location {
+        latitude: 35.7,
+        longitude: 51.4,
+        ..Default::default()
+    };
+    let times = calculator.calculate(&tehran, &NaiveDate::from_ymd_opt(2023, 3, 1).unwrap());
+
+    let noon = calculator.sun_coordinates(&tehran, &times.dhuhr.unwrap());
+    assert!(noon.hour_angle.abs() < 0.05, "{noon:?}");
+    assert!((noon.azimuth - 180.0).abs() < 0.1, "{noon:?}");
+
+    // the standard refraction of 0.833° includes the semi diameter of the sun, the upper limb is at the horizon
+    let sunset = calculator.sun_coordinates(&tehran, &times.sunset.unwrap());
+    assert!((sunset.altitude + 0.2667).abs() < 0.1, "{sunset:?}");
+    assert!(sunset.hour_angle > 0.0);
+
+    let fajr = calculator.sun_coordinates(&tehran, &times.fajr.unwrap());
+    assert!((fajr.altitude + 18.0).abs() < 0.05, "{fajr:?}");
+    assert!(fajr.hour_angle < -90.0);
+}
@@ -0,0 +1,161 @@
+use chrono::NaiveDate;
+use praytimes::{
+    hijri::UMM_AL_QURA,
+    methods,
+    solar::Vsop87,
+    timetable::DateRange,
+    types::{HighLatsMethod, Location, Parameters, Refinement, Rounding, RoundingPolicy},
+    Calculator,
+};
+
+mod common;
+use common::tehran;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+/// the rows of the timetable are the times of each day
+fn assert_daily(calculator: &Calculator, location: &Location, range: &DateRange) {
+    let timetable = calculator.timetable(location, range);
+    assert_eq!(timetable.rows.len(), range.len());
+    for (row, date) in timetable.rows.iter().zip(range.days()) {
+        assert_eq!(row.times, calculator.calculate(location, &date), "{date}");
+    }
+}
+
+#[test]
+fn should_cover_the_days_of_the_range() {
+    let february = DateRange::month(2024, 2).unwrap();
+    assert_eq!(
+        february,
+        DateRange::new(date(2024, 2, 1), date(2024, 2, 29))
+    );
+    assert_eq!(february.len(), 29);
+    assert_eq!(DateRange::month(2023, 12).unwrap().end, date(2023, 12, 31));
+    assert_eq!(DateRange::month(2023, 13), None);
+    assert_eq!(DateRange::year(2023).unwrap().days().count(), 365);
+
+    let empty = DateRange::new(date(2024, 2, 2), date(2024, 2, 1));
+    assert!(empty.is_empty());
+    assert_eq!(empty.days().count(), 0);
+}
+
+#[test]
+fn should_match_the_daily_calculation() {
+    let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    let range = DateRange::month(2024, 3).unwrap();
+    let timetable = calculator.timetable(&tehran(), &range);
+
+    assert_eq!(timetable.location, tehran());
+    assert_eq!(timetable.rows.len(), 31);
+    for (row, date) in timetable.rows.iter().zip(range.days()) {
+        assert_eq!(row.date, date);
+        assert_eq!(row.times, calculator.calculate(&tehran(), &date));
+    }
+}
+
+#[test]
+fn should_share_the_sun_positions_of_the_days() {
+    let range = DateRange::year(2024).unwrap();
+    let calculators = [
+        Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default()),
+        Calculator::new(
+            methods::ISLAMIC_SOCIETY_OF_NORTH_AMERICA,
+            Default::default(),
+        )
+        .with_solar_model(Vsop87),
+    ];
+    let locations = [
+        tehran(),
+        Location {
+            latitude: -33.9,
+            longitude: 151.2,
+            ..Default::default()
+        },
+        Location {
+            latitude: 59.9,
+            longitude: -170.0,
+            ..Default::default()
+        },
+    ];
+
+    // the same interpolated positions whatever the days calculated before
+    for calculator in &calculators {
+        for location in &locations {
+            assert_daily(calculator, location, &range);
+        }
+    }
+}
+
+#[test]
+fn should_follow_the_hijri_months() {
+    // ramadan 1445 from 2024-03-11 to 2024-04-09, with the isha of umm al-qura delayed
+    let calculator = Calculator::new(methods::UMM_AL_QURA_UNIVERSITY_MAKKAH, Default::default())
+        .with_hijri_calendar(UMM_AL_QURA);
+    let range = DateRange::new(date(2024, 2, 25), date(2024, 4, 20));
+    let timetable = calculator.timetable(&tehran(), &range);
+
+    for (row, date) in timetable.rows.iter().zip(range.days()) {
+        assert_eq!(row.times, calculator.calculate(&tehran(), &date));
+        assert_eq!(row.times.hijri, UMM_AL_QURA.to_hijri(&date));
+    }
+}
+
+#[test]
+fn should_converge_like_the_daily_calculation() {
+    let converge = Refinement::Converge {
+        tolerance: 1.0,
+        max_iterations: 10,
+    };
+    let range = DateRange::year(2024).unwrap();
+    let calculator =
+        Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default()).with_refinement(converge);
+    assert_daily(&calculator, &tehran(), &range);
+
+    // the nearest days searched for a day don't depend on the days before
+    let nearest_day = Calculator::new(
+        Parameters {
+            high_latitudes: HighLatsMethod::NearestDay,
+            ..methods::MUSLIM_WORLD_LEAGUE
+        },
+        Default::default(),
+    )
+    .with_refinement(converge);
+    let reykjavik = Location {
+        latitude: 64.1,
+        longitude: -21.9,
+        ..Default::default()
+    };
+    assert_daily(&nearest_day, &reykjavik, &range);
+}
+
+#[test]
+fn should_round_like_the_daily_calculation() {
+    let range = DateRange::year(2024).unwrap();
+    for rounding in [Rounding::Ceil, Rounding::Nearest, Rounding::Minutes(5)] {
+        let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default())
+            .with_rounding(RoundingPolicy::all(rounding));
+        assert_daily(&calculator, &tehran(), &range);
+    }
+}
+
+#[test]
+fn should_calculate_many_locations() {
+    let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    let locations: Vec<_> = [(21.4, 39.8), (51.5, -0.13), (-33.9, 151.2)]
+        .into_iter()
+        .map(|(latitude, longitude)| Location {
+            latitude,
+            longitude,
+            ..Default::default()
+        })
+        .collect();
+    let range = DateRange::month(2024, 6).unwrap();
+
+    let timetables = calculator.timetables(&locations, &range);
+    assert_eq!(timetables.len(), 3);
+    for (timetable, location) in timetables.iter().zip(&locations) {
+        assert_eq!(timetable, &calculator.timetable(location, &range));
+    }
+}
@@ -0,0 +1,116 @@
+use praytimes::{
+    error::CalcError,
+    methods,
+    types::{AsrFactor, Atmosphere, CalculationUnit, Degrees, Location, Parameters, PraytimeType},
+    Calculator,
+};
+
+mod common;
+use common::date;
+
+#[test]
+fn should_match_calculate_for_valid_inputs() {
+    let calculator = Calculator::new(
+        methods::INSTITUTE_OF_GEOPHYSICS_UNIVERSITY_OF_TEHRAN,
+        Default::default(),
+    );
+    let location = Location::new(35.7, 51.4, 1200.0).unwrap();
+
+    assert_eq!(
+        calculator.try_calculate(&location, &date()),
+        Ok(calculator.calculate(&location, &date()))
+    );
+}
+
+#[test]
+fn should_reject_invalid_locations() {
+    assert_eq!(
+        Location::new(200.0, 51.4, 0.0),
+        Err(CalcError::InvalidLatitude(200.0))
+    );
+    assert_eq!(
+        Location::new(35.7, -181.0, 0.0),
+        Err(CalcError::InvalidLongitude(-181.0))
+    );
+    assert_eq!(
+        Location::new(35.7, 51.4, -10.0),
+        Err(CalcError::InvalidElevation(-10.0))
+    );
+    assert!(matches!(
+        Location::new(f64::NAN, 51.4, 0.0),
+        Err(CalcError::InvalidLatitude(_))
+    ));
+
+    let location = Location {
+        atmosphere: Some(Atmosphere {
+            pressure: -1.0,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let calculator = Calculator::new(
+        methods::INSTITUTE_OF_GEOPHYSICS_UNIVERSITY_OF_TEHRAN,
+        Default::default(),
+    );
+    assert_eq!(
+        calculator.try_calculate(&location, &date()),
+        Err(CalcError::InvalidPressure(-1.0))
+    );
+}
+
+#[test]
+fn should_reject_invalid_parameters() {
+    let negative_asr = Parameters {
+        asr: AsrFactor { factor: -1.0 },
+        ..methods::MUSLIM_WORLD_LEAGUE
+    };
+    assert_eq!(
+        negative_asr.validated(),
+        Err(CalcError::InvalidAsrFactor(-1.0))
+    );
+
+    let isha = Parameters {
+        isha: CalculationUnit::Degrees(Degrees { degree: 120.0 }),
+        ..methods::MUSLIM_WORLD_LEAGUE
+    };
+    let calculator = Calculator::new(isha, Default::default());
+    let error = calculator
+        .try_calculate(&Location::default(), &date())
+        .unwrap_err();
+    assert_eq!(
+        error,
+        CalcError::InvalidAngle {
+            event: PraytimeType::Isha,
+            degree: 120.0
+        }
+    );
+    assert_eq!(error.to_string(), "isha angle 120 is not between 0 and 90");
+}
+
+#[test]
+fn should_accept_all_the_methods() {
+    for params in [
+        methods::MUSLIM_WORLD_LEAGUE,
+        methods::ISLAMIC_SOCIETY_OF_NORTH_AMERICA,
+        methods::EGYPTIAN_GENERAL_AUTHORITY_OF_SURVEY,
+        methods::UMM_AL_QURA_UNIVERSITY_MAKKAH,
+        methods::UNIVERSITY_OF_ISLAMIC_SCIENCES_KARACHI,
+        methods::INSTITUTE_OF_GEOPHYSICS_UNIVERSITY_OF_TEHRAN,
+        methods::SHIA_ITHNA_ASHARI_LEVA_INSTITUTE_QUM,
+        methods::MOONSIGHTING_COMMITTEE_WORLDWIDE,
+        methods::GULF_REGION,
+        methods::KUWAIT,
+        methods::QATAR,
+        methods::DUBAI,
+        methods::MAJLIS_UGAMA_ISLAM_SINGAPURA,
+        methods::JABATAN_KEMAJUAN_ISLAM_MALAYSIA,
+        methods::KEMENTERIAN_AGAMA_INDONESIA,
+        methods::DIYANET_ISLERI_BASKANLIGI,
+        methods::UNION_DES_ORGANISATIONS_ISLAMIQUES_DE_FRANCE,
+        methods::SPIRITUAL_ADMINISTRATION_OF_MUSLIMS_OF_RUSSIA,
+        methods::MINISTRY_OF_HABOUS_AND_ISLAMIC_AFFAIRS_MOROCCO,
+        methods::MINISTRY_OF_RELIGIOUS_AFFAIRS_ALGERIA,
+    ] {
+        assert_eq!(params.validate(), Ok(()), "{params:?}");
+    }
+}
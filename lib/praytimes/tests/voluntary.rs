@@ -0,0 +1,75 @@
+use chrono::{Duration, NaiveDateTime};
+use praytimes::{
+    forbidden::ForbiddenRules,
+    methods,
+    types::{MidnightMethod, Parameters},
+    Calculator,
+};
+
+mod common;
+use common::{date, tehran};
+
+fn seconds(later: Option<NaiveDateTime>, earlier: Option<NaiveDateTime>) -> f64 {
+    (later.unwrap() - earlier.unwrap()).num_milliseconds() as f64 / 1000.0
+}
+
+#[test]
+fn should_divide_the_night_of_the_midnight_method() {
+    for midnight in [MidnightMethod::Standard, MidnightMethod::Jafari] {
+        let calculator = Calculator::new(
+            Parameters {
+                midnight,
+                ..methods::MUSLIM_WORLD_LEAGUE
+            },
+            Default::default(),
+        );
+        let times = calculator.calculate(&tehran(), &date());
+        let voluntary = calculator.voluntary_times(&tehran(), &date(), &ForbiddenRules::default());
+
+        let half = seconds(times.midnight, times.sunset);
+        let portion = |time| seconds(time, times.sunset) / half / 2.0;
+        assert!((portion(voluntary.first_third) - 1.0 / 3.0).abs() < 1e-4);
+        assert!((portion(voluntary.last_third) - 2.0 / 3.0).abs() < 1e-4);
+        assert!((portion(voluntary.last_sixth) - 5.0 / 6.0).abs() < 1e-4);
+        assert_eq!(voluntary.tahajjud, voluntary.last_third);
+    }
+}
+
+#[test]
+fn should_end_the_jafari_night_at_fajr() {
+    let standard = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    let jafari = Calculator::new(
+        Parameters {
+            midnight: MidnightMethod::Jafari,
+            ..methods::MUSLIM_WORLD_LEAGUE
+        },
+        Default::default(),
+    );
+    let rules = ForbiddenRules::default();
+    let standard = standard.voluntary_times(&tehran(), &date(), &rules);
+    let jafari = jafari.voluntary_times(&tehran(), &date(), &rules);
+
+    assert!(jafari.last_third < standard.last_third);
+    assert_eq!(jafari.ishraq, standard.ishraq);
+}
+
+#[test]
+fn should_place_duha_between_ishraq_and_zawal() {
+    let calculator = Calculator::new(methods::MUSLIM_WORLD_LEAGUE, Default::default());
+    let rules = ForbiddenRules::default();
+    let times = calculator.calculate(&tehran(), &date());
+    let forbidden = calculator.forbidden_intervals(&tehran(), &date(), &rules);
+    let voluntary = calculator.voluntary_times(&tehran(), &date(), &rules);
+
+    assert_eq!(
+        voluntary.ishraq.unwrap() - times.sunrise.unwrap(),
+        Duration::minutes(15)
+    );
+    assert_eq!(voluntary.duha_end, Some(forbidden[1].start));
+    assert!(voluntary.ishraq < voluntary.duha_start);
+    assert!(voluntary.duha_start < voluntary.duha_end);
+
+    let quarter =
+        seconds(voluntary.duha_start, times.sunrise) / seconds(times.sunset, times.sunrise);
+    assert!((quarter - 0.25).abs() < 1e-4);
+}
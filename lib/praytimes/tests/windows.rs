@@ -0,0 +1,118 @@
+use chrono::Duration;
+use praytimes::{
+    forbidden::{ForbiddenLimit, ForbiddenRules},
+    types::{Degrees, NoonSide},
+    windows::{AsrEnd, IshaEnd, Madhab, WindowRules},
+};
+
+mod common;
+use common::{calculator, date, tehran};
+
+#[test]
+fn should_chain_the_windows_of_the_prayer_times() {
+    let times = calculator().calculate(&tehran(), &date());
+    let next = calculator().calculate(&tehran(), &date().succ_opt().unwrap());
+    let windows = calculator().prayer_windows(&tehran(), &date(), &WindowRules::default());
+
+    assert_eq!(windows.fajr.start, times.fajr);
+    assert_eq!(windows.fajr.end, times.sunrise);
+    assert_eq!(windows.dhuhr.start, times.dhuhr);
+    assert_eq!(windows.dhuhr.end, times.asr);
+    assert_eq!(windows.asr.end, times.sunset);
+    assert_eq!(windows.maghrib.start, times.maghrib);
+    assert_eq!(windows.maghrib.end, times.isha);
+    assert_eq!(windows.isha.start, times.isha);
+    assert_eq!(windows.isha.end, next.fajr);
+
+    for window in [
+        windows.fajr,
+        windows.dhuhr,
+        windows.asr,
+        windows.maghrib,
+        windows.isha,
+    ] {
+        assert_eq!(window.preferred_end, None);
+    }
+}
+
+#[test]
+fn should_end_asr_and_isha_by_the_rules() {
+    let times = calculator().calculate(&tehran(), &date());
+    let rules = WindowRules {
+        asr_end: AsrEnd::Yellowing,
+        isha_end: IshaEnd::Midnight,
+        ..Default::default()
+    };
+    let windows = calculator().prayer_windows(&tehran(), &date(), &rules);
+
+    assert_eq!(
+        times.sunset.unwrap() - windows.asr.end.unwrap(),
+        Duration::minutes(15)
+    );
+    assert_eq!(windows.isha.end, times.midnight);
+
+    let rules = WindowRules {
+        asr_end: AsrEnd::Yellowing,
+        yellowing: ForbiddenLimit::Altitude(Degrees { degree: 5.0 }),
+        ..Default::default()
+    };
+    let windows = calculator().prayer_windows(&tehran(), &date(), &rules);
+    let forbidden = calculator().forbidden_intervals(
+        &tehran(),
+        &date(),
+        &ForbiddenRules {
+            before_sunset: rules.yellowing,
+            ..Default::default()
+        },
+    );
+    assert_eq!(windows.asr.end, Some(forbidden[2].start));
+}
+
+#[test]
+fn should_give_the_preferred_windows_of_the_madhab() {
+    let rules = |madhab| WindowRules {
+        madhab: Some(madhab),
+        ..Default::default()
+    };
+    let double_shadow = calculator().shadow_time(&tehran(), &date(), 2.0, NoonSide::AfterNoon);
+
+    let shafii = calculator().prayer_windows(&tehran(), &date(), &rules(Madhab::Shafii));
+    assert_eq!(shafii.asr.preferred_end, double_shadow);
+    assert!(shafii.asr.start < shafii.asr.preferred_end);
+    assert!(shafii.asr.preferred_end < shafii.asr.end);
+
+    let hanafi = calculator().prayer_windows(&tehran(), &date(), &rules(Madhab::Hanafi));
+    let sunset = hanafi.asr.end.unwrap();
+    assert_eq!(
+        sunset - hanafi.asr.preferred_end.unwrap(),
+        Duration::minutes(15)
+    );
+
+    let first_third = calculator()
+        .voluntary_times(&tehran(), &date(), &ForbiddenRules::default())
+        .first_third;
+    assert_eq!(shafii.isha.preferred_end, first_third);
+    assert_eq!(hanafi.isha.preferred_end, first_third);
+    assert_eq!(shafii.fajr.preferred_end, None);
+}
+
+#[test]
+fn should_prefer_only_the_start_of_asr_and_isha() {
+    for madhab in [
+        Madhab::Hanafi,
+        Madhab::Maliki,
+        Madhab::Shafii,
+        Madhab::Hanbali,
+    ] {
+        let rules = WindowRules {
+            madhab: Some(madhab),
+            ..Default::default()
+        };
+        let windows = calculator().prayer_windows(&tehran(), &date(), &rules);
+        for window in [windows.fajr, windows.dhuhr, windows.maghrib] {
+            assert_eq!(window.preferred_end, None, "{madhab:?}");
+        }
+        assert!(windows.asr.preferred_end.is_some(), "{madhab:?}");
+        assert!(windows.isha.preferred_end.is_some(), "{madhab:?}");
+    }
+}
@@ -0,0 +1,117 @@
+use chrono::{Duration, NaiveDate, Timelike};
+use praytimes::{
+    types::{Location, Rounding, RoundingPolicy},
+    zoned::Tz,
+};
+
+mod common;
+use common::calculator;
+
+fn location(latitude: f64, longitude: f64) -> Location {
+    Location {
+        latitude,
+        longitude,
+        ..Default::default()
+    }
+}
+
+fn assert_on_local_day(location: &Location, zone: Tz, date: NaiveDate) {
+    let times = calculator().calculate_in(location, &date, &zone);
+    for time in [
+        times.fajr,
+        times.sunrise,
+        times.dhuhr,
+        times.asr,
+        times.sunset,
+        times.maghrib,
+    ] {
+        assert_eq!(time.unwrap().date_naive(), date, "{time:?}");
+    }
+    assert!(times.isha > times.maghrib);
+    assert!(times.midnight > times.isha);
+}
+
+#[test]
+fn should_use_the_local_day_far_from_utc() {
+    let date = NaiveDate::from_ymd_opt(2023, 3, 1).unwrap();
+
+    assert_on_local_day(&location(-36.85, 174.76), Tz::Pacific__Auckland, date);
+    assert_on_local_day(&location(21.31, -157.86), Tz::Pacific__Honolulu, date);
+    assert_on_local_day(&location(-18.14, 178.44), Tz::Pacific__Fiji, date);
+    // UTC+14 at a longitude of UTC-10
+    assert_on_local_day(&location(1.87, -157.43), Tz::Pacific__Kiritimati, date);
+}
+
+#[test]
+fn should_handle_transition_days() {
+    let london = location(51.5, -0.13);
+    let spring = NaiveDate::from_ymd_opt(2023, 3, 26).unwrap();
+    let autumn = NaiveDate::from_ymd_opt(2023, 10, 29).unwrap();
+
+    let before =
+        calculator().calculate_in(&london, &spring.pred_opt().unwrap(), &Tz::Europe__London);
+    let times = calculator().calculate_in(&london, &spring, &Tz::Europe__London);
+    assert!(!before.has_transition());
+    assert!(times.has_transition());
+    assert_eq!(times.day_length(), Duration::hours(23));
+    // dhuhr moves an hour later on the clock
+    assert_eq!(
+        times.dhuhr.unwrap().hour(),
+        before.dhuhr.unwrap().hour() + 1
+    );
+
+    let times = calculator().calculate_in(&london, &autumn, &Tz::Europe__London);
+    assert!(times.has_transition());
+    assert_eq!(times.day_length(), Duration::hours(25));
+}
+
+#[test]
+fn should_format_in_the_zone() {
+    let date = NaiveDate::from_ymd_opt(2023, 3, 1).unwrap();
+    let times = calculator().calculate_in(&location(-36.85, 174.76), &date, &Tz::Pacific__Auckland);
+    let formatted = times.format_times("%F %z");
+
+    assert_eq!(formatted.dhuhr.unwrap(), "2023-03-01 +1300");
+}
+
+#[test]
+fn should_round_on_the_local_clock() {
+    let kathmandu = location(27.7, 85.3);
+    let date = NaiveDate::from_ymd_opt(2023, 6, 1).unwrap();
+    let times = calculator()
+        .with_rounding(RoundingPolicy::all(Rounding::Minutes(10)))
+        .calculate_in(&kathmandu, &date, &Tz::Asia__Kathmandu);
+
+    for time in [
+        times.fajr,
+        times.dhuhr,
+        times.asr,
+        times.maghrib,
+        times.isha,
+    ] {
+        let time = time.unwrap();
+        assert_eq!(time.minute() % 10, 0, "{time}");
+        assert_eq!(time.second(), 0, "{time}");
+    }
+}
+
+#[test]
+fn should_skip_the_days_missing_in_the_zone() {
+    // Samoa moved west of the date line, 2011-12-29 was followed by 2011-12-31
+    let apia = location(-13.83, -171.76);
+    let skipped = NaiveDate::from_ymd_opt(2011, 12, 30).unwrap();
+    let times = calculator().calculate_in(&apia, &skipped, &Tz::Pacific__Apia);
+
+    assert!(times.is_skipped());
+    assert_eq!(times.day_length(), Duration::zero());
+    assert_eq!(times.fajr, None);
+    assert_eq!(times.dhuhr, None);
+    assert_eq!(times.midnight, None);
+    assert!(times.adjusted.is_empty());
+
+    for date in [skipped.pred_opt().unwrap(), skipped.succ_opt().unwrap()] {
+        let times = calculator().calculate_in(&apia, &date, &Tz::Pacific__Apia);
+        assert!(!times.is_skipped());
+        assert_eq!(times.dhuhr.unwrap().date_naive(), date);
+    }
+}